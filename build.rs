@@ -0,0 +1,59 @@
+//! In `registry` mode, mounts all solutions in `src/bin` as modules of the main binary.
+//! See `template::registry` for how these are run.
+use std::{env, fs, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    if env::var_os("CARGO_FEATURE_REGISTRY").is_none() {
+        return;
+    }
+
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let bin_dir = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("src/bin");
+
+    // solution bins are named after their puzzle, e.g. `2023-01.rs`.
+    let mut names: Vec<String> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+                .filter_map(|name| name.strip_suffix(".rs").map(String::from))
+                .filter(|name| {
+                    name.split_once('-').is_some_and(|(year, day)| {
+                        year.len() == 4
+                            && day.len() == 2
+                            && (year.chars().chain(day.chars())).all(|c| c.is_ascii_digit())
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    names.sort_unstable();
+
+    // each solution declares a global allocator when profiling, which can only exist once per binary.
+    if env::var_os("CARGO_FEATURE_DHAT_HEAP").is_some() {
+        names.clear();
+    }
+
+    let mut out = String::new();
+
+    for name in &names {
+        let path = bin_dir.join(format!("{name}.rs"));
+        out += &format!(
+            "#[allow(dead_code)]\n#[path = {:?}]\nmod solution_{};\n",
+            path.display().to_string(),
+            name.replace('-', "_")
+        );
+    }
+
+    out += "\npub static SOLUTIONS: &[advent_of_code::template::registry::Solution] = &[\n";
+    for name in &names {
+        out += &format!("    solution_{}::SOLUTION,\n", name.replace('-', "_"));
+    }
+    out += "];\n";
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("solutions.rs");
+    fs::write(out_path, out).unwrap();
+}
@@ -0,0 +1,259 @@
+//! Cycle detection for simulations that repeat, e.g. when a puzzle asks for the state after a billion steps.
+//!
+//! ```
+//! use advent_of_code::cycle;
+//!
+//! // 0, 1, 2, 3, 1, 2, 3, ...
+//! let step = |x: &u32| if *x == 3 { 1 } else { x + 1 };
+//! assert_eq!(cycle::nth_state(0, step, 1_000_000_000), 1);
+//! ```
+
+use std::{collections::HashMap, hash::Hash};
+
+/// A cycle in a sequence of states, where the state at `start + length` equals the state at `start`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The first step whose state equals the state at step `n`.
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// Finds a cycle with Floyd's tortoise and hare, storing only two states at a time.
+/// Never returns if the sequence does not repeat.
+pub fn floyd<S: Clone + PartialEq>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        hare = step(&hare);
+    }
+
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Finds a cycle with Brent's algorithm, storing only two states at a time and usually calling `step` less often than [`floyd`].
+/// Never returns if the sequence does not repeat.
+pub fn brent<S: Clone + PartialEq>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    let (mut power, mut length) = (1, 1);
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    let mut start = 0;
+    tortoise = initial.clone();
+    hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Finds a cycle by remembering every state. Returns the cycle and the states up to where it repeats.
+/// Never returns if the sequence does not repeat.
+pub fn find_cycle<S: Clone + Eq + Hash>(
+    initial: S,
+    mut step: impl FnMut(&S) -> S,
+) -> (Cycle, Vec<S>) {
+    let mut history = History::new();
+    let mut state = initial;
+
+    loop {
+        if let Some(cycle) = history.push(state.clone()) {
+            return (cycle, history.states);
+        }
+        state = step(&state);
+    }
+}
+
+/// Computes the state after `n` steps, skipping ahead once the states repeat.
+pub fn nth_state<S: Clone + Eq + Hash>(initial: S, mut step: impl FnMut(&S) -> S, n: usize) -> S {
+    let mut history = History::new();
+    let mut state = initial;
+
+    for _ in 0..n {
+        if let Some(cycle) = history.push(state.clone()) {
+            return history.states[cycle.reduce(n)].clone();
+        }
+        state = step(&state);
+    }
+
+    state
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The states of a simulation so far, for loops that need more control than [`find_cycle`].
+#[derive(Clone, Debug)]
+pub struct History<S> {
+    states: Vec<S>,
+    steps: HashMap<S, usize>,
+}
+
+impl<S: Clone + Eq + Hash> History<S> {
+    pub fn new() -> Self {
+        Self {
+            states: vec![],
+            steps: HashMap::new(),
+        }
+    }
+
+    /// Records the state of the next step. If the state was seen before, it is not recorded and the cycle is returned.
+    pub fn push(&mut self, state: S) -> Option<Cycle> {
+        if let Some(&start) = self.steps.get(&state) {
+            return Some(Cycle {
+                start,
+                length: self.states.len() - start,
+            });
+        }
+
+        self.steps.insert(state.clone(), self.states.len());
+        self.states.push(state);
+        None
+    }
+
+    /// The recorded states, indexed by step.
+    pub fn states(&self) -> &[S] {
+        &self.states
+    }
+}
+
+impl<S: Clone + Eq + Hash> Default for History<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{brent, find_cycle, floyd, nth_state, Cycle, History};
+
+    /// 0, 1, ..., 10, 4, 5, ...
+    fn step(x: &u32) -> u32 {
+        if *x < 10 {
+            x + 1
+        } else {
+            4
+        }
+    }
+
+    /// A pseudo-random sequence with a long tail before the cycle.
+    fn pseudo_random(x: &u64) -> u64 {
+        (x * x + 7) % 1009
+    }
+
+    fn brute_force(initial: u64) -> Cycle {
+        let mut states = vec![initial];
+        loop {
+            let next = pseudo_random(states.last().unwrap());
+            if let Some(start) = states.iter().position(|s| *s == next) {
+                return Cycle {
+                    start,
+                    length: states.len() - start,
+                };
+            }
+            states.push(next);
+        }
+    }
+
+    #[test]
+    fn finds_cycles() {
+        let expected = Cycle {
+            start: 4,
+            length: 7,
+        };
+        assert_eq!(floyd(0, step), expected);
+        assert_eq!(brent(0, step), expected);
+
+        let (cycle, states) = find_cycle(0, step);
+        assert_eq!(cycle, expected);
+        assert_eq!(states, (0..=10).collect::<Vec<_>>());
+
+        for initial in [0, 1, 2, 500] {
+            let expected = brute_force(initial);
+            assert_eq!(floyd(initial, pseudo_random), expected);
+            assert_eq!(brent(initial, pseudo_random), expected);
+            assert_eq!(find_cycle(initial, pseudo_random).0, expected);
+        }
+    }
+
+    #[test]
+    fn finds_cycles_of_length_one() {
+        let expected = Cycle {
+            start: 3,
+            length: 1,
+        };
+        let step = |x: &u32| (*x + 1).min(3);
+        assert_eq!(floyd(0, step), expected);
+        assert_eq!(brent(0, step), expected);
+        assert_eq!(find_cycle(0, step).0, expected);
+    }
+
+    #[test]
+    fn extrapolates_states() {
+        for n in 0..50 {
+            let expected = (0..n).fold(0, |state, _| step(&state));
+            assert_eq!(nth_state(0, step, n), expected);
+        }
+        assert_eq!(nth_state(0, step, 1_000_000_000), 6);
+    }
+
+    #[test]
+    fn records_history() {
+        let mut history = History::new();
+        assert_eq!(history.push('a'), None);
+        assert_eq!(history.push('b'), None);
+        assert_eq!(
+            history.push('b'),
+            Some(Cycle {
+                start: 1,
+                length: 1
+            })
+        );
+        assert_eq!(history.states(), &['a', 'b']);
+    }
+}
@@ -0,0 +1,169 @@
+use std::{error::Error, fmt::Display, str::FromStr};
+
+use crate::point::Point2;
+
+/// A compass direction on a [`Grid`](crate::grid::Grid), where north points up.
+///
+/// # Parsing
+/// Directions parse from `U`/`D`/`L`/`R`, `^`/`v`/`<`/`>` and `N`/`E`/`S`/`W` as well as `NE`/`SE`/`SW`/`NW`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    /// The four orthogonal directions, clockwise starting north.
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// All eight directions, clockwise starting north.
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    /// The change of position when moving one step, with `y` growing downwards.
+    pub const fn offset(self) -> Point2 {
+        match self {
+            Direction::North => Point2::new(0, -1),
+            Direction::NorthEast => Point2::new(1, -1),
+            Direction::East => Point2::new(1, 0),
+            Direction::SouthEast => Point2::new(1, 1),
+            Direction::South => Point2::new(0, 1),
+            Direction::SouthWest => Point2::new(-1, 1),
+            Direction::West => Point2::new(-1, 0),
+            Direction::NorthWest => Point2::new(-1, -1),
+        }
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        !Self::CARDINAL.contains(&self)
+    }
+
+    /// Turns 90 degrees counter-clockwise.
+    pub fn turn_left(self) -> Self {
+        self.rotate(6)
+    }
+
+    /// Turns 90 degrees clockwise.
+    pub fn turn_right(self) -> Self {
+        self.rotate(2)
+    }
+
+    /// Turns 180 degrees.
+    pub fn reverse(self) -> Self {
+        self.rotate(4)
+    }
+
+    /// Rotates clockwise in steps of 45 degrees.
+    fn rotate(self, steps: usize) -> Self {
+        Self::ALL[(self as usize + steps) % Self::ALL.len()]
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = DirectionFromStrError;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'U' | '^' | 'N' => Ok(Direction::North),
+            'R' | '>' | 'E' => Ok(Direction::East),
+            'D' | 'v' | 'S' => Ok(Direction::South),
+            'L' | '<' | 'W' => Ok(Direction::West),
+            _ => Err(DirectionFromStrError),
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = DirectionFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "NE" => Ok(Direction::NorthEast),
+            "SE" => Ok(Direction::SouthEast),
+            "SW" => Ok(Direction::SouthWest),
+            "NW" => Ok(Direction::NorthWest),
+            _ => {
+                let mut chars = s.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => c.try_into(),
+                    _ => Err(DirectionFromStrError),
+                }
+            }
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`Direction`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct DirectionFromStrError;
+
+impl Error for DirectionFromStrError {}
+
+impl Display for DirectionFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one of U/D/L/R, ^/v/</>, N/E/S/W or NE/SE/SW/NW")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{Direction, DirectionFromStrError};
+
+    #[test]
+    fn turns() {
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::West.turn_right(), Direction::North);
+        assert_eq!(Direction::NorthEast.turn_right(), Direction::SouthEast);
+        assert_eq!(Direction::SouthWest.turn_left(), Direction::SouthEast);
+        assert_eq!(Direction::East.reverse(), Direction::West);
+        assert_eq!(Direction::NorthWest.reverse(), Direction::SouthEast);
+    }
+
+    #[test]
+    fn parses_directions() {
+        for (input, direction) in [
+            ("U", Direction::North),
+            ("v", Direction::South),
+            ("<", Direction::West),
+            ("R", Direction::East),
+            ("E", Direction::East),
+            ("NW", Direction::NorthWest),
+        ] {
+            assert_eq!(input.parse(), Ok(direction));
+        }
+
+        assert_eq!(Direction::try_from('^'), Ok(Direction::North));
+        assert_eq!("X".parse::<Direction>(), Err(DirectionFromStrError));
+        assert_eq!("UU".parse::<Direction>(), Err(DirectionFromStrError));
+        assert_eq!("".parse::<Direction>(), Err(DirectionFromStrError));
+    }
+
+    #[test]
+    fn classifies_diagonals() {
+        assert!(!Direction::South.is_diagonal());
+        assert!(Direction::SouthWest.is_diagonal());
+        assert_eq!(Direction::ALL.iter().filter(|d| d.is_diagonal()).count(), 4);
+    }
+}
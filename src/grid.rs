@@ -0,0 +1,592 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+use crate::{direction::Direction, point::Point2};
+
+/// A rectangular grid of cells, e.g. a map from the puzzle input.
+/// Cells are addressed by `(x, y)` or a [`Point2`], starting top-left.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum GridError {
+    Empty,
+    /// A row has a different length than the first row. Lines are counted from 1.
+    RaggedRow {
+        line: usize,
+        expected: usize,
+        actual: usize,
+    },
+    /// A character could not be parsed into a cell. Lines and columns are counted from 1.
+    InvalidCell {
+        line: usize,
+        column: usize,
+        value: char,
+    },
+}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridError::Empty => write!(f, "grid is empty."),
+            GridError::RaggedRow {
+                line,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "line {line}: expected row of length {expected}, found {actual}."
+            ),
+            GridError::InvalidCell {
+                line,
+                column,
+                value,
+            } => write!(f, "line {line}, column {column}: invalid cell `{value}`."),
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid by calling `f(x, y)` for every cell.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| f(x, y))
+            .collect();
+
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Creates a grid where every cell has the same value.
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Parses puzzle text with one row per line, converting each character with `f`.
+    pub fn parse_with(input: &str, f: impl Fn(char) -> Option<T>) -> Result<Self, GridError> {
+        let mut cells = vec![];
+        let mut width = None;
+        let mut height = 0;
+
+        for (y, line) in input.lines().enumerate() {
+            let len = line.chars().count();
+
+            match width {
+                None => width = Some(len),
+                Some(expected) if expected != len => {
+                    return Err(GridError::RaggedRow {
+                        line: y + 1,
+                        expected,
+                        actual: len,
+                    });
+                }
+                _ => {}
+            }
+
+            for (x, c) in line.chars().enumerate() {
+                cells.push(f(c).ok_or(GridError::InvalidCell {
+                    line: y + 1,
+                    column: x + 1,
+                    value: c,
+                })?);
+            }
+
+            height += 1;
+        }
+
+        match width {
+            Some(width) if width > 0 => Ok(Self {
+                width,
+                height,
+                cells,
+            }),
+            _ => Err(GridError::Empty),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether a point lies within the grid.
+    pub fn contains(&self, point: Point2) -> bool {
+        self.index_of_point(point).is_some()
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.index_of(x, y).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        self.index_of(x, y).map(|i| &mut self.cells[i])
+    }
+
+    pub fn get_point(&self, point: Point2) -> Option<&T> {
+        self.index_of_point(point).map(|i| &self.cells[i])
+    }
+
+    pub fn get_point_mut(&mut self, point: Point2) -> Option<&mut T> {
+        self.index_of_point(point).map(|i| &mut self.cells[i])
+    }
+
+    /// Gets a cell as if the grid repeated infinitely in every direction.
+    ///
+    /// # Panics
+    /// Panics if the grid is empty.
+    pub fn get_wrapping(&self, point: Point2) -> &T {
+        #[allow(
+            clippy::cast_possible_wrap,
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss
+        )]
+        let (x, y) = (
+            point.x.rem_euclid(self.width as i64) as usize,
+            point.y.rem_euclid(self.height as i64) as usize,
+        );
+        &self[(x, y)]
+    }
+
+    /// Iterates all cells with their position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point2, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    /// Iterates all cells mutably with their position, row by row.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Point2, &mut T)> {
+        self.points().zip(self.cells.iter_mut())
+    }
+
+    /// Iterates the positions of all cells, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point2> {
+        let (width, height) = (self.width, self.height);
+        (0..height).flat_map(move |y| (0..width).map(move |x| to_point(x, y)))
+    }
+
+    /// Iterates the orthogonal neighbours of a point that lie within the grid, clockwise starting north.
+    pub fn neighbours_4(&self, point: Point2) -> impl Iterator<Item = (Point2, &T)> {
+        self.neighbours(point, &Direction::CARDINAL)
+    }
+
+    /// Iterates the orthogonal and diagonal neighbours of a point that lie within the grid, clockwise starting north.
+    pub fn neighbours_8(&self, point: Point2) -> impl Iterator<Item = (Point2, &T)> {
+        self.neighbours(point, &Direction::ALL)
+    }
+
+    fn neighbours<'a>(
+        &'a self,
+        point: Point2,
+        directions: &'static [Direction],
+    ) -> impl Iterator<Item = (Point2, &'a T)> {
+        directions.iter().filter_map(move |&direction| {
+            let neighbour = point.step(direction);
+            self.get_point(neighbour).map(|cell| (neighbour, cell))
+        })
+    }
+
+    /// A row of the grid, top to bottom.
+    ///
+    /// # Panics
+    /// Panics if the row is out of bounds.
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height, "row {y} out of bounds.");
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    /// A column of the grid, left to right.
+    ///
+    /// # Panics
+    /// Panics if the column is out of bounds.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} out of bounds.");
+        (0..self.height).map(move |y| &self[(x, y)])
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Iterates the diagonals going down and to the right (`↘`), starting at the bottom-left corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = Vec<&T>> {
+        (0..self.diagonal_count()).map(|i| {
+            let start = if i < self.height {
+                (0, self.height - 1 - i)
+            } else {
+                (i - self.height + 1, 0)
+            };
+            self.walk(start, |(x, y)| Some((x + 1, y + 1)))
+        })
+    }
+
+    /// Iterates the diagonals going down and to the left (`↙`), starting at the top-left corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = Vec<&T>> {
+        (0..self.diagonal_count()).map(|i| {
+            let start = if i < self.width {
+                (i, 0)
+            } else {
+                (self.width - 1, i - self.width + 1)
+            };
+            self.walk(start, |(x, y)| Some((x.checked_sub(1)?, y + 1)))
+        })
+    }
+
+    fn diagonal_count(&self) -> usize {
+        if self.cells.is_empty() {
+            0
+        } else {
+            self.width + self.height - 1
+        }
+    }
+
+    fn walk(
+        &self,
+        start: (usize, usize),
+        step: impl Fn((usize, usize)) -> Option<(usize, usize)>,
+    ) -> Vec<&T> {
+        let mut cells = vec![];
+        let mut current = Some(start);
+
+        while let Some(cell) = current.and_then(|(x, y)| self.get(x, y)) {
+            cells.push(cell);
+            current = current.and_then(&step);
+        }
+
+        cells
+    }
+
+    /// Position of the first cell matching a predicate, row by row.
+    pub fn position(&self, predicate: impl Fn(&T) -> bool) -> Option<Point2> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(p, _)| p)
+    }
+
+    /// Positions of all cells matching a predicate, row by row.
+    pub fn positions<'a>(
+        &'a self,
+        predicate: impl Fn(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Point2> + 'a {
+        self.iter()
+            .filter(move |(_, cell)| predicate(cell))
+            .map(|(p, _)| p)
+    }
+
+    /// Position of the first cell equal to `value`, row by row.
+    pub fn find(&self, value: &T) -> Option<Point2>
+    where
+        T: PartialEq,
+    {
+        self.position(|cell| cell == value)
+    }
+
+    /// Converts every cell with `f`.
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Mirrors the grid along its main diagonal, swapping rows and columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.height, self.width, |x, y| self[(y, x)].clone())
+    }
+
+    /// Rotates the grid by 90 degrees clockwise.
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.height, self.width, |x, y| {
+            self[(y, self.height - 1 - x)].clone()
+        })
+    }
+
+    /// Rotates the grid by 90 degrees counter-clockwise.
+    pub fn rotate_counter_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.height, self.width, |x, y| {
+            self[(self.width - 1 - y, x)].clone()
+        })
+    }
+
+    /// Mirrors the grid left to right.
+    pub fn flip_horizontal(&self) -> Self
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.width, self.height, |x, y| {
+            self[(self.width - 1 - x, y)].clone()
+        })
+    }
+
+    /// Mirrors the grid top to bottom.
+    pub fn flip_vertical(&self) -> Self
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.width, self.height, |x, y| {
+            self[(x, self.height - 1 - y)].clone()
+        })
+    }
+
+    fn index_of(&self, x: usize, y: usize) -> Option<usize> {
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+
+    fn index_of_point(&self, point: Point2) -> Option<usize> {
+        let x = usize::try_from(point.x).ok()?;
+        let y = usize::try_from(point.y).ok()?;
+        self.index_of(x, y)
+    }
+}
+
+#[allow(clippy::cast_possible_wrap)]
+fn to_point(x: usize, y: usize) -> Point2 {
+    Point2::new(x as i64, y as i64)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Grid<char> {
+    type Err = GridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::parse_with(s, Some)
+    }
+}
+
+/// Prints one row per line, without a trailing newline. For `Grid<char>`, this is the format the grid was parsed from.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        self.get(x, y)
+            .unwrap_or_else(|| panic!("({x}, {y}) out of bounds."))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
+        self.get_mut(x, y)
+            .unwrap_or_else(|| panic!("({x}, {y}) out of bounds."))
+    }
+}
+
+impl<T> Index<Point2> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point2) -> &Self::Output {
+        self.get_point(point)
+            .unwrap_or_else(|| panic!("{point} out of bounds."))
+    }
+}
+
+impl<T> IndexMut<Point2> for Grid<T> {
+    fn index_mut(&mut self, point: Point2) -> &mut Self::Output {
+        self.get_point_mut(point)
+            .unwrap_or_else(|| panic!("{point} out of bounds."))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{Grid, GridError};
+    use crate::{direction::Direction, point::Point2};
+
+    fn get_mock_grid() -> Grid<char> {
+        "abc\ndef".parse().unwrap()
+    }
+
+    fn collect<'a>(cells: impl Iterator<Item = &'a char>) -> String {
+        cells.collect()
+    }
+
+    #[test]
+    fn parses_grids() {
+        let grid = get_mock_grid();
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid[(2, 1)], 'f');
+        assert_eq!(grid[Point2::new(1, 0)], 'b');
+    }
+
+    #[test]
+    fn parses_with_conversion() {
+        let grid = Grid::parse_with("12\n34\n", |c| c.to_digit(10)).unwrap();
+        assert_eq!(grid[(1, 1)], 4);
+
+        assert_eq!(
+            Grid::parse_with("12\n3x", |c| c.to_digit(10)),
+            Err(GridError::InvalidCell {
+                line: 2,
+                column: 2,
+                value: 'x'
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_grids() {
+        assert_eq!(
+            "abc\nde".parse::<Grid<char>>(),
+            Err(GridError::RaggedRow {
+                line: 2,
+                expected: 3,
+                actual: 2
+            })
+        );
+        assert_eq!("".parse::<Grid<char>>(), Err(GridError::Empty));
+    }
+
+    #[test]
+    fn round_trips_display() {
+        let input = "#..#\n.##.\n#..#\n";
+        let grid: Grid<char> = input.parse().unwrap();
+        assert_eq!(grid.to_string(), input.trim_end());
+    }
+
+    #[test]
+    fn checks_bounds() {
+        let mut grid = get_mock_grid();
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.get_point(Point2::new(-1, 0)), None);
+        assert!(grid.contains(Point2::new(2, 1)));
+        assert!(!grid.contains(Point2::new(2, 2)));
+
+        *grid.get_mut(0, 0).unwrap() = 'z';
+        grid[Point2::new(1, 1)] = 'y';
+        assert_eq!(grid.to_string(), "zbc\ndyf");
+    }
+
+    #[test]
+    fn wraps_around() {
+        let grid = get_mock_grid();
+        assert_eq!(*grid.get_wrapping(Point2::new(3, 2)), 'a');
+        assert_eq!(*grid.get_wrapping(Point2::new(-1, -1)), 'f');
+    }
+
+    #[test]
+    fn iterates_neighbours() {
+        let grid: Grid<char> = "abc\ndef\nghi".parse().unwrap();
+
+        let neighbours: String = grid
+            .neighbours_4(Point2::new(1, 1))
+            .map(|(_, c)| c)
+            .collect();
+        assert_eq!(neighbours, "bfhd");
+
+        let neighbours: String = grid
+            .neighbours_8(Point2::new(1, 1))
+            .map(|(_, c)| c)
+            .collect();
+        assert_eq!(neighbours, "bcfihgda");
+
+        let neighbours: Vec<Point2> = grid
+            .neighbours_4(Point2::new(0, 0))
+            .map(|(p, _)| p)
+            .collect();
+        assert_eq!(neighbours, vec![Point2::new(1, 0), Point2::new(0, 1)]);
+    }
+
+    #[test]
+    fn iterates_rows_and_columns() {
+        let grid = get_mock_grid();
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(collect(grid.column(1)), "be");
+
+        let columns: Vec<String> = grid.columns().map(collect).collect();
+        assert_eq!(columns, vec!["ad", "be", "cf"]);
+    }
+
+    #[test]
+    fn iterates_diagonals() {
+        let grid = get_mock_grid();
+
+        let diagonals: Vec<String> = grid.diagonals().map(|d| collect(d.into_iter())).collect();
+        assert_eq!(diagonals, vec!["d", "ae", "bf", "c"]);
+
+        let diagonals: Vec<String> = grid
+            .anti_diagonals()
+            .map(|d| collect(d.into_iter()))
+            .collect();
+        assert_eq!(diagonals, vec!["a", "bd", "ce", "f"]);
+    }
+
+    #[test]
+    fn transforms_grids() {
+        let grid = get_mock_grid();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_counter_clockwise().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed");
+        assert_eq!(grid.flip_vertical().to_string(), "def\nabc");
+        assert_eq!(grid.rotate_clockwise().rotate_counter_clockwise(), grid);
+    }
+
+    #[test]
+    fn finds_cells() {
+        let grid: Grid<char> = "#.S\n..#".parse().unwrap();
+        assert_eq!(grid.find(&'S'), Some(Point2::new(2, 0)));
+        assert_eq!(grid.find(&'E'), None);
+        assert_eq!(grid.position(|c| *c == '.'), Some(Point2::new(1, 0)));
+
+        let walls: Vec<Point2> = grid.positions(|c| *c == '#').collect();
+        assert_eq!(walls, vec![Point2::new(0, 0), Point2::new(2, 1)]);
+    }
+
+    #[test]
+    fn maps_cells() {
+        let grid = Grid::from_fn(2, 2, |x, y| x + y);
+        assert_eq!(grid.map(|x| x * 10).to_string(), "010\n1020");
+        assert_eq!(Grid::filled(2, 1, 0).to_string(), "00");
+    }
+}
@@ -0,0 +1,532 @@
+//! Integer intervals and sets of them, e.g. for puzzles that map or split large ranges of numbers.
+//!
+//! ```
+//! use advent_of_code::interval::{Interval, PiecewiseMap, RangeSet};
+//!
+//! // seeds 79..93 through the map "52 50 48"
+//! let mut map = PiecewiseMap::new();
+//! map.insert(Interval::new(50, 98), 2);
+//! let soil = map.map_set(&RangeSet::from(Interval::new(79, 93)));
+//! assert_eq!(soil.intervals(), &[Interval::new(81, 95)]);
+//! ```
+
+use std::ops::Range;
+
+/// A half-open interval `start..end` of integers. Intervals with `end <= start` are empty.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl Interval {
+    pub const fn new(start: i64, end: i64) -> Self {
+        Self { start, end }
+    }
+
+    /// The interval `start..=end`, as puzzles often describe ranges.
+    pub const fn inclusive(start: i64, end: i64) -> Self {
+        Self::new(start, end + 1)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    /// The number of integers in the interval.
+    pub fn len(&self) -> u64 {
+        if self.is_empty() {
+            0
+        } else {
+            self.end.abs_diff(self.start)
+        }
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// The overlap of two intervals, if there is any.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let intersection = Self::new(self.start.max(other.start), self.end.min(other.end));
+        (!intersection.is_empty()).then_some(intersection)
+    }
+
+    /// Moves the interval by `offset`.
+    pub fn shift(&self, offset: i64) -> Self {
+        Self::new(self.start + offset, self.end + offset)
+    }
+}
+
+impl From<Range<i64>> for Interval {
+    fn from(range: Range<i64>) -> Self {
+        Self::new(range.start, range.end)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A set of integers, stored as sorted intervals that neither overlap nor touch.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct RangeSet {
+    intervals: Vec<Interval>,
+}
+
+impl RangeSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn intervals(&self) -> &[Interval] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The number of integers in the set.
+    pub fn len(&self) -> u64 {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        let i = self.intervals.partition_point(|i| i.end <= value);
+        self.intervals.get(i).is_some_and(|i| i.contains(value))
+    }
+
+    pub fn insert(&mut self, interval: Interval) {
+        self.intervals.push(interval);
+        self.normalize();
+    }
+
+    pub fn remove(&mut self, interval: Interval) {
+        *self = self.difference(&interval.into());
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = Self {
+            intervals: [self.intervals.as_slice(), other.intervals.as_slice()].concat(),
+        };
+        union.normalize();
+        union
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intervals = vec![];
+        let (mut i, mut j) = (0, 0);
+
+        while let (Some(a), Some(b)) = (self.intervals.get(i), other.intervals.get(j)) {
+            intervals.extend(a.intersection(b));
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        Self { intervals }
+    }
+
+    /// The integers of this set that are not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut intervals = vec![];
+        let mut j = 0;
+
+        for &interval in &self.intervals {
+            let mut rest = interval;
+
+            // skip intervals of `other` that end before this one starts.
+            while other.intervals.get(j).is_some_and(|b| b.end <= rest.start) {
+                j += 1;
+            }
+
+            for b in other.intervals[j..]
+                .iter()
+                .take_while(|b| b.start < rest.end)
+            {
+                if b.start > rest.start {
+                    intervals.push(Interval::new(rest.start, b.start));
+                }
+                rest.start = rest.start.max(b.end);
+            }
+
+            if !rest.is_empty() {
+                intervals.push(rest);
+            }
+        }
+
+        Self { intervals }
+    }
+
+    /// Moves every interval by `offset`.
+    pub fn shift(&self, offset: i64) -> Self {
+        Self {
+            intervals: self.intervals.iter().map(|i| i.shift(offset)).collect(),
+        }
+    }
+
+    fn normalize(&mut self) {
+        self.intervals.retain(|i| !i.is_empty());
+        self.intervals.sort_unstable();
+
+        let mut merged: Vec<Interval> = Vec::with_capacity(self.intervals.len());
+        for interval in self.intervals.drain(..) {
+            match merged.last_mut() {
+                Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+                _ => merged.push(interval),
+            }
+        }
+
+        self.intervals = merged;
+    }
+}
+
+impl From<Interval> for RangeSet {
+    fn from(interval: Interval) -> Self {
+        std::iter::once(interval).collect()
+    }
+}
+
+impl FromIterator<Interval> for RangeSet {
+    fn from_iter<T: IntoIterator<Item = Interval>>(iter: T) -> Self {
+        let mut set = Self {
+            intervals: iter.into_iter().collect(),
+        };
+        set.normalize();
+        set
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A function that shifts pieces of the number line by an offset, e.g. a seed-to-soil map.
+/// Values outside of all pieces map to themselves. If pieces overlap, the first inserted piece wins.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PiecewiseMap {
+    pieces: Vec<(Interval, i64)>,
+}
+
+impl PiecewiseMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps the values of `source` to `source` shifted by `offset`.
+    pub fn insert(&mut self, source: Interval, offset: i64) {
+        self.pieces.push((source, offset));
+    }
+
+    pub fn map(&self, value: i64) -> i64 {
+        self.pieces
+            .iter()
+            .find(|(source, _)| source.contains(value))
+            .map_or(value, |(_, offset)| value + offset)
+    }
+
+    /// Maps every value of a set, splitting its intervals where pieces begin and end.
+    pub fn map_set(&self, set: &RangeSet) -> RangeSet {
+        let mut unmapped = set.clone();
+        let mut mapped = RangeSet::new();
+
+        for &(source, offset) in &self.pieces {
+            let source = RangeSet::from(source);
+            mapped = mapped.union(&unmapped.intersection(&source).shift(offset));
+            unmapped = unmapped.difference(&source);
+        }
+
+        mapped.union(&unmapped)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An axis-aligned box of integer points in `N` dimensions, e.g. a cuboid for `N = 3`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Cuboid<const N: usize> {
+    pub axes: [Interval; N],
+}
+
+impl<const N: usize> Cuboid<N> {
+    pub const fn new(axes: [Interval; N]) -> Self {
+        Self { axes }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.axes.iter().any(Interval::is_empty)
+    }
+
+    /// The number of integer points in the box.
+    pub fn volume(&self) -> u64 {
+        self.axes.iter().map(Interval::len).product()
+    }
+
+    pub fn contains(&self, point: [i64; N]) -> bool {
+        self.axes
+            .iter()
+            .zip(point)
+            .all(|(axis, x)| axis.contains(x))
+    }
+
+    /// The overlap of two boxes, if there is any.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let mut axes = self.axes;
+        for (axis, other) in axes.iter_mut().zip(&other.axes) {
+            *axis = axis.intersection(other)?;
+        }
+        Some(Self { axes })
+    }
+
+    /// Splits off the parts of this box that are not in `other`, as up to `2 * N` disjoint boxes.
+    pub fn difference(&self, other: &Self) -> Vec<Self> {
+        let Some(overlap) = self.intersection(other) else {
+            return if self.is_empty() { vec![] } else { vec![*self] };
+        };
+
+        let mut parts = vec![];
+        let mut rest = *self;
+
+        for (axis, overlap) in overlap.axes.iter().enumerate() {
+            let current = rest.axes[axis];
+
+            for piece in [
+                Interval::new(current.start, overlap.start),
+                Interval::new(overlap.end, current.end),
+            ] {
+                if !piece.is_empty() {
+                    let mut part = rest;
+                    part.axes[axis] = piece;
+                    parts.push(part);
+                }
+            }
+
+            rest.axes[axis] = *overlap;
+        }
+
+        parts
+    }
+}
+
+/// A set of integer points in `N` dimensions, stored as disjoint boxes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CuboidSet<const N: usize> {
+    cuboids: Vec<Cuboid<N>>,
+}
+
+impl<const N: usize> CuboidSet<N> {
+    pub fn new() -> Self {
+        Self { cuboids: vec![] }
+    }
+
+    pub fn cuboids(&self) -> &[Cuboid<N>] {
+        &self.cuboids
+    }
+
+    /// The number of integer points in the set.
+    pub fn volume(&self) -> u64 {
+        self.cuboids.iter().map(Cuboid::volume).sum()
+    }
+
+    pub fn contains(&self, point: [i64; N]) -> bool {
+        self.cuboids.iter().any(|c| c.contains(point))
+    }
+
+    pub fn insert(&mut self, cuboid: Cuboid<N>) {
+        self.remove(&cuboid);
+        if !cuboid.is_empty() {
+            self.cuboids.push(cuboid);
+        }
+    }
+
+    pub fn remove(&mut self, cuboid: &Cuboid<N>) {
+        self.cuboids = self
+            .cuboids
+            .iter()
+            .flat_map(|c| c.difference(cuboid))
+            .collect();
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::{Cuboid, CuboidSet, Interval, PiecewiseMap, RangeSet};
+
+    /// A xorshift generator, so property tests are reproducible.
+    struct Random(u64);
+
+    impl Random {
+        fn below(&mut self, n: u64) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0 % n
+        }
+
+        fn between(&mut self, min: i64, max: i64) -> i64 {
+            min + i64::try_from(self.below(max.abs_diff(min))).unwrap()
+        }
+
+        fn interval(&mut self) -> Interval {
+            let start = self.between(-20, 20);
+            Interval::new(start, start + self.between(-2, 10))
+        }
+
+        fn set(&mut self) -> RangeSet {
+            (0..self.below(5)).map(|_| self.interval()).collect()
+        }
+    }
+
+    fn values(set: &RangeSet) -> BTreeSet<i64> {
+        set.intervals()
+            .iter()
+            .flat_map(|i| i.start..i.end)
+            .collect()
+    }
+
+    fn assert_normalized(set: &RangeSet) {
+        for pair in set.intervals().windows(2) {
+            assert!(pair[0].end < pair[1].start, "{set:?} is not normalized.");
+        }
+        assert!(set.intervals().iter().all(|i| !i.is_empty()));
+    }
+
+    #[test]
+    fn computes_intervals() {
+        let interval = Interval::inclusive(2, 5);
+        assert_eq!(interval.len(), 4);
+        assert!(interval.contains(5));
+        assert!(!interval.contains(6));
+        assert_eq!(
+            interval.intersection(&Interval::new(4, 10)),
+            Some(Interval::new(4, 6))
+        );
+        assert_eq!(interval.intersection(&Interval::new(6, 10)), None);
+        assert_eq!(Interval::new(3, 1).len(), 0);
+    }
+
+    #[test]
+    fn merges_ranges() {
+        let set: RangeSet = [
+            Interval::new(5, 8),
+            Interval::new(0, 2),
+            Interval::new(2, 3),
+            Interval::new(7, 9),
+            Interval::new(4, 4),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(set.intervals(), &[Interval::new(0, 3), Interval::new(5, 9)]);
+        assert_eq!(set.len(), 7);
+        assert!(set.contains(8));
+        assert!(!set.contains(3));
+
+        let mut set = set;
+        set.remove(Interval::new(1, 6));
+        assert_eq!(set.intervals(), &[Interval::new(0, 1), Interval::new(6, 9)]);
+    }
+
+    #[test]
+    fn matches_brute_force_set_operations() {
+        let mut random = Random(0x2545_f491_4f6c_dd1d);
+
+        for _ in 0..500 {
+            let (a, b) = (random.set(), random.set());
+            let (values_a, values_b) = (values(&a), values(&b));
+
+            for (result, expected) in [
+                (a.union(&b), &values_a | &values_b),
+                (a.intersection(&b), &values_a & &values_b),
+                (a.difference(&b), &values_a - &values_b),
+            ] {
+                assert_normalized(&result);
+                assert_eq!(values(&result), expected, "{a:?} {b:?}");
+                assert_eq!(result.len(), expected.len() as u64);
+            }
+
+            for x in -25..35 {
+                assert_eq!(a.contains(x), values_a.contains(&x));
+            }
+        }
+    }
+
+    #[test]
+    fn matches_brute_force_mappings() {
+        let mut random = Random(0x9e37_79b9_7f4a_7c15);
+
+        for _ in 0..500 {
+            let mut map = PiecewiseMap::new();
+            for _ in 0..random.below(4) {
+                map.insert(random.interval(), random.between(-15, 15));
+            }
+
+            let set = random.set();
+            let mapped = map.map_set(&set);
+            let expected: BTreeSet<i64> = values(&set).into_iter().map(|x| map.map(x)).collect();
+
+            assert_normalized(&mapped);
+            assert_eq!(values(&mapped), expected, "{map:?} {set:?}");
+        }
+    }
+
+    #[test]
+    fn splits_cuboids() {
+        let a = Cuboid::new([Interval::new(0, 3); 3]);
+        let b = Cuboid::new([Interval::new(1, 2); 3]);
+
+        assert_eq!(a.volume(), 27);
+        assert_eq!(a.intersection(&b), Some(b));
+
+        let parts = a.difference(&b);
+        assert_eq!(parts.len(), 6);
+        assert_eq!(parts.iter().map(Cuboid::volume).sum::<u64>(), 26);
+        assert!(parts.iter().all(|part| part.intersection(&b).is_none()));
+    }
+
+    #[test]
+    fn matches_brute_force_cuboids() {
+        let mut random = Random(0xdead_beef_cafe_f00d);
+        let mut cuboid = |random: &mut Random| {
+            Cuboid::new([(); 3].map(|()| {
+                let start = random.between(0, 6);
+                Interval::new(start, start + random.between(-1, 5))
+            }))
+        };
+
+        for _ in 0..100 {
+            let mut set = CuboidSet::new();
+            let mut expected = BTreeSet::new();
+
+            for _ in 0..8 {
+                let c = cuboid(&mut random);
+                let points = (0..12)
+                    .flat_map(|x| (0..12).flat_map(move |y| (0..12).map(move |z| [x, y, z])))
+                    .filter(|&p| c.contains(p));
+
+                if random.below(3) == 0 {
+                    set.remove(&c);
+                    for p in points {
+                        expected.remove(&p);
+                    }
+                } else {
+                    set.insert(c);
+                    expected.extend(points);
+                }
+
+                assert_eq!(set.volume(), expected.len() as u64);
+            }
+
+            for &p in &expected {
+                assert!(set.contains(p));
+            }
+        }
+    }
+}
@@ -1,3 +1,19 @@
 pub mod template;
 
 // Use this file to add helper functions and additional modules.
+
+pub mod cycle;
+pub mod direction;
+pub mod grid;
+pub mod interval;
+pub mod math;
+pub mod memo;
+pub mod ocr;
+pub mod parsing;
+pub mod point;
+pub mod search;
+
+pub use direction::Direction;
+pub use grid::Grid;
+pub use interval::{Cuboid, CuboidSet, Interval, PiecewiseMap, RangeSet};
+pub use point::{Point2, Point3};
@@ -1,75 +1,137 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, history, read, scaffold, solve, time, verify,
+};
 use args::{parse, AppArguments};
+use std::process;
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
+use advent_of_code::template::PuzzleId;
+
+#[cfg(feature = "registry")]
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
 
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::{Day, PuzzleId, Year};
     use std::process;
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            puzzle: PuzzleId,
         },
         Read {
-            day: Day,
+            puzzle: PuzzleId,
+        },
+        History {
+            puzzle: PuzzleId,
         },
         Scaffold {
-            day: Day,
+            puzzle: PuzzleId,
             download: bool,
             overwrite: bool,
+            template: Option<String>,
+            answer_type: Option<String>,
         },
         Solve {
-            day: Day,
+            puzzle: PuzzleId,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
         },
         All {
+            year: Year,
             release: bool,
+            jobs: usize,
         },
         Time {
+            year: Year,
             all: bool,
             day: Option<Day>,
             store: bool,
+            compare: bool,
+            threshold: Option<f64>,
+        },
+        Verify {
+            year: Year,
+            all: bool,
+            day: Option<Day>,
+            release: bool,
         },
         #[cfg(feature = "today")]
         Today,
     }
 
+    /// Resolves the year passed via `--year`, falling back to the `AOC_YEAR` environment variable.
+    fn resolve_year(year: Option<Year>) -> Result<Year, Box<dyn std::error::Error>> {
+        year.or_else(Year::from_env).ok_or_else(|| {
+            "no year specified. Pass `--year <year>` or set `AOC_YEAR` in `.cargo/config.toml`."
+                .into()
+        })
+    }
+
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
+        // NOTE: options need to be consumed before free arguments.
+        let year: Option<Year> = args.opt_value_from_str("--year")?;
+
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
+                year: resolve_year(year)?,
                 release: args.contains("--release"),
+                jobs: args.opt_value_from_str(["-j", "--jobs"])?.unwrap_or(1),
+            },
+            Some("time") if args.contains("--history") => AppArguments::History {
+                puzzle: PuzzleId::new(resolve_year(year)?, args.free_from_str()?),
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let compare = args.contains("--compare");
+                let threshold = args.opt_value_from_str("--threshold")?;
 
                 AppArguments::Time {
+                    year: resolve_year(year)?,
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    compare,
+                    threshold,
+                }
+            }
+            Some("verify") => {
+                let all = args.contains("--all");
+                let release = args.contains("--release");
+
+                AppArguments::Verify {
+                    year: resolve_year(year)?,
+                    all,
+                    day: args.opt_free_from_str()?,
+                    release,
                 }
             }
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                puzzle: PuzzleId::new(resolve_year(year)?, args.free_from_str()?),
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
-            },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
-                download: args.contains("--download"),
-                overwrite: args.contains("--overwrite"),
+                puzzle: PuzzleId::new(resolve_year(year)?, args.free_from_str()?),
             },
+            Some("scaffold") => {
+                let download = args.contains("--download");
+                let overwrite = args.contains("--overwrite");
+                let template = args.opt_value_from_str("--template")?;
+                let answer_type = args.opt_value_from_str("--answer-type")?;
+                AppArguments::Scaffold {
+                    puzzle: PuzzleId::new(resolve_year(year)?, args.free_from_str()?),
+                    download,
+                    overwrite,
+                    template,
+                    answer_type,
+                }
+            }
             Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+                puzzle: PuzzleId::new(resolve_year(year)?, args.free_from_str()?),
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
@@ -96,35 +158,85 @@ mod args {
 }
 
 fn main() {
+    #[cfg(feature = "registry")]
+    advent_of_code::template::registry::register(solutions::SOLUTIONS);
+
     match parse() {
         Err(err) => {
             eprintln!("Error: {err}");
-            std::process::exit(1);
+            process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day, download, overwrite } => {
-                scaffold::handle(day, overwrite);
+            AppArguments::All {
+                year,
+                release,
+                jobs,
+            } => all::handle(year, release, jobs),
+            AppArguments::Time {
+                year,
+                day,
+                all,
+                store,
+                compare,
+                threshold,
+            } => time::handle(
+                year,
+                day,
+                all,
+                store,
+                compare,
+                threshold.unwrap_or(time::DEFAULT_REGRESSION_THRESHOLD),
+            ),
+            AppArguments::Verify {
+                year,
+                day,
+                all,
+                release,
+            } => verify::handle(year, day, all, release),
+            AppArguments::Download { puzzle } => {
+                if let Err(e) = download::handle(puzzle) {
+                    eprintln!("failed to download puzzle: {e}");
+                    process::exit(1);
+                }
+            }
+            AppArguments::Read { puzzle } => read::handle(puzzle),
+            AppArguments::History { puzzle } => history::handle(puzzle),
+            AppArguments::Scaffold {
+                puzzle,
+                download,
+                overwrite,
+                template,
+                answer_type,
+            } => {
+                // download first, so the template can use the puzzle description.
+                // without it, the plain template is scaffolded.
                 if download {
-                    download::handle(day);
+                    if let Err(e) = download::handle(puzzle) {
+                        eprintln!("failed to download puzzle, scaffolding without it: {e}");
+                    }
                 }
+                scaffold::handle(
+                    puzzle,
+                    overwrite,
+                    template.as_deref(),
+                    answer_type.as_deref(),
+                );
             }
             AppArguments::Solve {
-                day,
+                puzzle,
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+            } => solve::handle(puzzle, release, dhat, submit),
             #[cfg(feature = "today")]
             AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+                match PuzzleId::today() {
+                    Some(puzzle) => {
+                        if let Err(e) = download::handle(puzzle) {
+                            eprintln!("failed to download puzzle, scaffolding without it: {e}");
+                        }
+                        scaffold::handle(puzzle, false, None, None);
+                        read::handle(puzzle)
                     }
                     None => {
                         eprintln!(
@@ -0,0 +1,392 @@
+use std::{
+    fmt::Display,
+    ops::{Add, Div, Mul, Rem, Sub},
+    sync::Once,
+};
+
+/// Primitive integer types, so helpers work with the integer size a solution uses.
+pub trait Integer:
+    Copy
+    + Ord
+    + Display
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn wrapping_add(self, rhs: Self) -> Self;
+    fn wrapping_sub(self, rhs: Self) -> Self;
+    fn wrapping_mul(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, rhs)
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, rhs)
+                }
+
+                fn wrapping_add(self, rhs: Self) -> Self {
+                    <$t>::wrapping_add(self, rhs)
+                }
+
+                fn wrapping_sub(self, rhs: Self) -> Self {
+                    <$t>::wrapping_sub(self, rhs)
+                }
+
+                fn wrapping_mul(self, rhs: Self) -> Self {
+                    <$t>::wrapping_mul(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/* -------------------------------------------------------------------------- */
+
+/// The greatest common divisor, which is never negative.
+///
+/// # Panics
+/// Panics if the gcd does not fit into `T`, which only happens for `gcd(T::MIN, 0)` and `gcd(T::MIN, T::MIN)`.
+pub fn gcd<T: Integer>(mut a: T, mut b: T) -> T {
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+
+    if a < T::ZERO {
+        T::ZERO
+            .checked_sub(a)
+            .unwrap_or_else(|| panic!("gcd overflows: {a} has no positive counterpart"))
+    } else {
+        a
+    }
+}
+
+/// The least common multiple, which is never negative.
+///
+/// # Panics
+/// Panics like [`gcd`] for `lcm(T::MIN, T::MIN)`.
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    if a == T::ZERO || b == T::ZERO {
+        return T::ZERO;
+    }
+
+    let lcm = a / gcd(a, b) * b;
+    if lcm < T::ZERO {
+        T::ZERO - lcm
+    } else {
+        lcm
+    }
+}
+
+/// The greatest common divisor of all values, `0` if there are none.
+pub fn gcd_all<T: Integer>(values: impl IntoIterator<Item = T>) -> T {
+    values.into_iter().fold(T::ZERO, gcd)
+}
+
+/// The least common multiple of all values, `1` if there are none.
+/// E.g. the step at which several cycles first line up again.
+pub fn lcm_all<T: Integer>(values: impl IntoIterator<Item = T>) -> T {
+    values.into_iter().fold(T::ONE, lcm)
+}
+
+/// The extended Euclidean algorithm. Returns `(gcd, x, y)` such that `a * x + b * y == gcd`.
+///
+/// Returns `None` if the gcd does not fit into an `i64`, which only happens when it is `2^63`,
+/// e.g. for `a == i64::MIN` and `b == 0`.
+pub fn extended_gcd(a: i64, b: i64) -> Option<(i64, i64, i64)> {
+    let (g, x, y) = extended_gcd_wide(a.into(), b.into());
+    // the coefficients are at most half of `|a|` or `|b|`, so only the gcd can overflow.
+    Some((
+        i64::try_from(g).ok()?,
+        i64::try_from(x).ok()?,
+        i64::try_from(y).ok()?,
+    ))
+}
+
+fn extended_gcd_wide(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (1, 0);
+    let (mut y0, mut y1) = (0, 1);
+
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+
+    if r0 < 0 {
+        (-r0, -x0, -y0)
+    } else {
+        (r0, x0, y0)
+    }
+}
+
+/// The inverse of `a` modulo `modulus`, if `a` and `modulus` are coprime. Returns `None` for a modulus of `0`.
+pub fn mod_inverse(a: i64, modulus: i64) -> Option<i64> {
+    if modulus == 0 {
+        return None;
+    }
+
+    let (g, x, _) = extended_gcd(a, modulus)?;
+    (g == 1).then(|| x.rem_euclid(modulus))
+}
+
+/// Solves a system of congruences `x ≡ residue (mod modulus)` with the Chinese Remainder Theorem.
+///
+/// Returns the smallest non-negative solution and the modulus it repeats with, or `None` if there is no solution,
+/// a modulus is `0` or the combined modulus does not fit into an `i64`. Moduli do not need to be coprime.
+pub fn crt(congruences: impl IntoIterator<Item = (i64, i64)>) -> Option<(i64, i64)> {
+    let (mut residue, mut modulus) = (0_i128, 1_i128);
+
+    for (r, m) in congruences {
+        if m == 0 {
+            return None;
+        }
+
+        let (r, m) = (i128::from(r), i128::from(m));
+        let (g, p, _) = extended_gcd_wide(modulus, m);
+        let diff = r - residue;
+
+        if diff % g != 0 {
+            return None;
+        }
+
+        let step = m / g;
+        let k = (diff / g % step * p % step).rem_euclid(step);
+        // both factors fit into an `i64`, so only the check against `i64::MAX` is needed.
+        let lcm = modulus * step;
+        if lcm > i128::from(i64::MAX) {
+            return None;
+        }
+
+        residue = (residue + modulus * k).rem_euclid(lcm);
+        modulus = lcm;
+    }
+
+    Some((i64::try_from(residue).ok()?, i64::try_from(modulus).ok()?))
+}
+
+/// Computes `base ^ exp % modulus` by repeated squaring.
+///
+/// # Panics
+/// Panics if `modulus` is `0`.
+pub fn mod_pow(base: u64, mut exp: u64, modulus: u64) -> u64 {
+    assert!(modulus != 0, "mod_pow with a modulus of 0");
+    let modulus = u128::from(modulus);
+    let mut base = u128::from(base) % modulus;
+    let mut result = 1 % modulus;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+
+    // the result is smaller than the `u64` modulus.
+    result as u64
+}
+
+/// The integer square root, i.e. the largest integer whose square is at most `n`.
+pub fn isqrt(n: u64) -> u64 {
+    n.isqrt()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Adds, panicking on overflow in debug builds like `+` does, but warning on stderr in release builds where `+` wraps silently.
+pub fn add_checked<T: Integer>(a: T, b: T) -> T {
+    a.checked_add(b)
+        .unwrap_or_else(|| overflowed("add", a, b, a.wrapping_add(b)))
+}
+
+/// Subtracts, panicking on overflow in debug builds like `-` does, but warning on stderr in release builds where `-` wraps silently.
+pub fn sub_checked<T: Integer>(a: T, b: T) -> T {
+    a.checked_sub(b)
+        .unwrap_or_else(|| overflowed("subtract", a, b, a.wrapping_sub(b)))
+}
+
+/// Multiplies, panicking on overflow in debug builds like `*` does, but warning on stderr in release builds where `*` wraps silently.
+pub fn mul_checked<T: Integer>(a: T, b: T) -> T {
+    a.checked_mul(b)
+        .unwrap_or_else(|| overflowed("multiply", a, b, a.wrapping_mul(b)))
+}
+
+/// Sums and multiplies iterators with [`add_checked`] and [`mul_checked`].
+pub trait CheckedIterator<T: Integer>: Iterator<Item = T> + Sized {
+    fn sum_checked(self) -> T {
+        self.fold(T::ZERO, add_checked)
+    }
+
+    fn product_checked(self) -> T {
+        self.fold(T::ONE, mul_checked)
+    }
+}
+
+impl<T: Integer, I: Iterator<Item = T>> CheckedIterator<T> for I {}
+
+static OVERFLOW_WARNING: Once = Once::new();
+
+fn overflowed<T: Integer>(operation: &str, a: T, b: T, wrapped: T) -> T {
+    let type_name = std::any::type_name::<T>();
+
+    if cfg!(debug_assertions) {
+        panic!("attempt to {operation} `{a}` and `{b}` with overflow of {type_name}.");
+    }
+
+    OVERFLOW_WARNING.call_once(|| {
+        eprintln!(
+            "Warning: attempt to {operation} `{a}` and `{b}` overflowed {type_name}, the answer is likely wrong. Consider using a larger integer type."
+        );
+    });
+
+    wrapped
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{
+        add_checked, crt, extended_gcd, gcd, gcd_all, isqrt, lcm, lcm_all, mod_inverse, mod_pow,
+        mul_checked, sub_checked, CheckedIterator,
+    };
+
+    #[test]
+    fn computes_gcd_and_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12_i32, 18), 6);
+        assert_eq!(gcd(0_u8, 5), 5);
+        assert_eq!(lcm(4_u64, 6), 12);
+        assert_eq!(lcm(-4_i64, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+
+        assert_eq!(gcd_all([12, 18, 27]), 3);
+        assert_eq!(gcd_all(Vec::<u32>::new()), 0);
+        assert_eq!(lcm_all([2_u64, 3, 4, 5]), 60);
+        assert_eq!(lcm_all(Vec::<u32>::new()), 1);
+        assert_eq!(gcd(i64::MIN, 6), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "gcd overflows")]
+    fn panics_on_gcd_overflow() {
+        gcd(i64::MIN, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "modulus of 0")]
+    fn panics_on_zero_modulus() {
+        mod_pow(2, 3, 0);
+    }
+
+    #[test]
+    fn computes_bezout_coefficients() {
+        for (a, b) in [(240, 46), (46, 240), (-7, 3), (0, 5), (17, 0)] {
+            let (g, x, y) = extended_gcd(a, b).unwrap();
+            assert_eq!(g, gcd(a, b));
+            assert_eq!(a * x + b * y, g);
+        }
+
+        assert_eq!(extended_gcd(i64::MIN, 0), None);
+        assert_eq!(extended_gcd(i64::MIN, i64::MIN), None);
+
+        for (a, b) in [
+            (i64::MIN, 6),
+            (i64::MIN, i64::MAX),
+            (i64::MAX, i64::MIN + 2),
+        ] {
+            let (g, x, y) = extended_gcd(a, b).unwrap();
+            assert!(g > 0);
+            assert_eq!(
+                i128::from(a) * i128::from(x) + i128::from(b) * i128::from(y),
+                i128::from(g)
+            );
+        }
+    }
+
+    #[test]
+    fn computes_inverses() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+        assert_eq!(mod_inverse(3, 0), None);
+    }
+
+    #[test]
+    fn solves_congruences() {
+        // buses depart at offsets from the timestamp.
+        let buses = [(0, 7), (1, 13), (4, 59), (6, 31), (7, 19)];
+        let congruences = buses.map(|(offset, bus)| (-offset, bus));
+        assert_eq!(crt(congruences), Some((1_068_781, 7 * 13 * 59 * 31 * 19)));
+
+        assert_eq!(crt([(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt([]), Some((0, 1)));
+        assert_eq!(crt([(1, 4), (2, 0)]), None);
+
+        // pairwise coprime, but their product does not fit into an `i64`.
+        let moduli = [(1 << 62) + 1, (1 << 62) + 3, (1 << 62) + 5];
+        assert_eq!(crt(moduli.map(|m| (1, m))), None);
+        assert_eq!(
+            crt([(1, (1 << 31) + 1), (2, (1 << 31) - 1)]).unwrap().1,
+            (1 << 62) - 1
+        );
+    }
+
+    #[test]
+    fn computes_powers() {
+        assert_eq!(mod_pow(4, 13, 497), 445);
+        // Fermat's little theorem
+        assert_eq!(mod_pow(u64::MAX, 1_000_000_006, 1_000_000_007), 1);
+        assert_eq!(mod_pow(u64::MAX - 1, 2, u64::MAX), 1);
+        assert_eq!(mod_pow(7, 0, 1), 0);
+    }
+
+    #[test]
+    fn computes_square_roots() {
+        assert_eq!(isqrt(0), 0);
+        assert_eq!(isqrt(15), 3);
+        assert_eq!(isqrt(16), 4);
+        assert_eq!(isqrt(u64::MAX), u64::from(u32::MAX));
+    }
+
+    #[test]
+    fn computes_checked_arithmetic() {
+        assert_eq!(add_checked(2_u32, 3), 5);
+        assert_eq!(sub_checked(2_i8, 3), -1);
+        assert_eq!(mul_checked(1 << 20, 1_u64 << 20), 1 << 40);
+        assert_eq!([1_u32, 2, 3].into_iter().sum_checked(), 6);
+        assert_eq!([2_u32, 3, 4].into_iter().product_checked(), 24);
+    }
+
+    #[test]
+    #[should_panic(expected = "overflow of u32")]
+    fn panics_on_overflow_in_debug() {
+        [u32::MAX, 1].into_iter().sum_checked();
+    }
+}
@@ -0,0 +1,113 @@
+//! Memoization for recursive solvers.
+//!
+//! The function receives a `recurse` callback for its recursive calls, so results of every call are cached:
+//!
+//! ```
+//! use advent_of_code::memo::Memo;
+//!
+//! let mut fibonacci = Memo::new(|recurse, n: u64| {
+//!     if n < 2 {
+//!         n
+//!     } else {
+//!         recurse(n - 1) + recurse(n - 2)
+//!     }
+//! });
+//! assert_eq!(fibonacci.get(90), 2_880_067_194_370_816_120);
+//! ```
+
+use std::{collections::HashMap, hash::Hash};
+
+/// A function whose results are cached by argument. Use tuples as keys for multiple arguments.
+pub struct Memo<K, V, F> {
+    cache: HashMap<K, V>,
+    f: F,
+}
+
+impl<K, V, F> Memo<K, V, F>
+where
+    K: Clone + Eq + Hash,
+    V: Clone,
+    F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+{
+    pub fn new(f: F) -> Self {
+        Self {
+            cache: HashMap::new(),
+            f,
+        }
+    }
+
+    /// The result for `key`, computing it only if it is not cached yet.
+    pub fn get(&mut self, key: K) -> V {
+        Self::call(&mut self.cache, &self.f, key)
+    }
+
+    /// The number of cached results.
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Forgets all cached results, e.g. between parts that use different inputs.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+
+    fn call(cache: &mut HashMap<K, V>, f: &F, key: K) -> V {
+        if let Some(value) = cache.get(&key) {
+            return value.clone();
+        }
+
+        let value = f(&mut |key| Self::call(cache, f, key), key.clone());
+        cache.insert(key, value.clone());
+        value
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::Memo;
+
+    #[test]
+    fn memoizes_recursion() {
+        let mut fibonacci = Memo::new(|recurse, n: u64| {
+            if n < 2 {
+                n
+            } else {
+                recurse(n - 1) + recurse(n - 2)
+            }
+        });
+
+        assert_eq!(fibonacci.get(90), 2_880_067_194_370_816_120);
+        assert_eq!(fibonacci.len(), 91);
+
+        fibonacci.clear();
+        assert!(fibonacci.is_empty());
+    }
+
+    #[test]
+    fn memoizes_multiple_arguments() {
+        // stones split or grow on every blink.
+        let mut stones = Memo::new(|recurse, (stone, blinks): (u64, u32)| -> u64 {
+            if blinks == 0 {
+                return 1;
+            }
+
+            let digits = stone.checked_ilog10().unwrap_or(0) + 1;
+            if stone == 0 {
+                recurse((1, blinks - 1))
+            } else if digits % 2 == 0 {
+                let half = 10_u64.pow(digits / 2);
+                recurse((stone / half, blinks - 1)) + recurse((stone % half, blinks - 1))
+            } else {
+                recurse((stone * 2024, blinks - 1))
+            }
+        });
+
+        assert_eq!(stones.get((125, 25)) + stones.get((17, 25)), 55312);
+    }
+}
@@ -0,0 +1,395 @@
+//! Recognizes the block letters that some Advent of Code puzzles draw on a screen.
+//!
+//! ```
+//! use advent_of_code::ocr;
+//!
+//! let screen = "#..#.###.\n#..#.#..#\n####.###.\n#..#.#..#\n#..#.#..#\n#..#.###.";
+//! assert_eq!(ocr::decode(screen), Ok("HB".into()));
+//! ```
+use std::collections::{BTreeSet, HashSet};
+use std::fmt::Display;
+
+/// Letters of the 4x6 font, e.g. used in 2016 day 8, 2019 day 8 & 11, 2021 day 13 and 2022 day 10.
+const SMALL_LETTERS: [(char, &str); 18] = [
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', ".###\n..#.\n..#.\n..#.\n..#.\n.###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+/// Letters of the 6x10 font used in 2018 day 10.
+const LARGE_LETTERS: [(char, &str); 15] = [
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+/// A glyph that does not match any letter of the font.
+#[derive(Debug, PartialEq, Eq)]
+pub struct UnknownGlyph {
+    /// Position of the glyph among all glyphs on the screen, starting at 0.
+    pub index: usize,
+    /// The glyph, drawn with `#` and `.`.
+    pub glyph: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum OcrError {
+    Empty,
+    UnsupportedHeight(usize),
+    UnknownGlyphs(Vec<UnknownGlyph>),
+}
+
+impl Display for OcrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OcrError::Empty => write!(f, "no letters found."),
+            OcrError::UnsupportedHeight(height) => {
+                write!(
+                    f,
+                    "letters of height {height} are not supported, expected 6 or 10."
+                )
+            }
+            OcrError::UnknownGlyphs(glyphs) => {
+                write!(f, "could not recognize {} glyph(s):", glyphs.len())?;
+                for UnknownGlyph { index, glyph } in glyphs {
+                    write!(f, "\n\nglyph {index}:\n{glyph}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A block of pixels, stored row by row.
+type Pixels = Vec<Vec<bool>>;
+
+/// Something letters are drawn on, which can be turned into rows of pixels.
+pub trait Screen {
+    fn pixels(&self) -> Pixels;
+}
+
+/// Text with lit pixels drawn as `#` (or `█`), and dark pixels as `.` or spaces.
+impl Screen for str {
+    fn pixels(&self) -> Pixels {
+        self.lines()
+            .map(|line| line.chars().map(|c| c == '#' || c == '█').collect())
+            .collect()
+    }
+}
+
+impl Screen for String {
+    fn pixels(&self) -> Pixels {
+        self.as_str().pixels()
+    }
+}
+
+/// Rows of pixels, where `true` is lit.
+impl Screen for Vec<Vec<bool>> {
+    fn pixels(&self) -> Pixels {
+        self.clone()
+    }
+}
+
+/// Coordinates `(x, y)` of lit pixels. The screen starts at the top-left-most coordinate.
+impl<T: Copy + TryInto<i64>> Screen for HashSet<(T, T)> {
+    fn pixels(&self) -> Pixels {
+        pixels_from_points(self.iter().copied())
+    }
+}
+
+/// Coordinates `(x, y)` of lit pixels. The screen starts at the top-left-most coordinate.
+impl<T: Copy + TryInto<i64>> Screen for BTreeSet<(T, T)> {
+    fn pixels(&self) -> Pixels {
+        pixels_from_points(self.iter().copied())
+    }
+}
+
+fn pixels_from_points<T: TryInto<i64>>(points: impl Iterator<Item = (T, T)>) -> Pixels {
+    let points: Vec<(i64, i64)> = points
+        .filter_map(|(x, y)| Some((x.try_into().ok()?, y.try_into().ok()?)))
+        .collect();
+
+    let (Some(min_x), Some(min_y)) = (
+        points.iter().map(|p| p.0).min(),
+        points.iter().map(|p| p.1).min(),
+    ) else {
+        return vec![];
+    };
+
+    let mut pixels: Pixels = vec![];
+
+    for (x, y) in points {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let (x, y) = ((x - min_x) as usize, (y - min_y) as usize);
+
+        if pixels.len() <= y {
+            pixels.resize(y + 1, vec![]);
+        }
+        if pixels[y].len() <= x {
+            pixels[y].resize(x + 1, false);
+        }
+        pixels[y][x] = true;
+    }
+
+    pixels
+}
+
+/// Decodes the letters on a screen. The font is picked by the height of the letters.
+pub fn decode<S: Screen + ?Sized>(screen: &S) -> Result<String, OcrError> {
+    let rows: Pixels = screen
+        .pixels()
+        .into_iter()
+        .filter(|row| row.iter().any(|x| *x))
+        .collect();
+
+    if rows.is_empty() {
+        return Err(OcrError::Empty);
+    }
+
+    let letters: &[(char, &str)] = match rows.len() {
+        6 => &SMALL_LETTERS,
+        10 => &LARGE_LETTERS,
+        height => return Err(OcrError::UnsupportedHeight(height)),
+    };
+
+    let mut text = String::new();
+    let mut unknown: Vec<UnknownGlyph> = vec![];
+
+    for (index, glyph) in split_glyphs(&rows).into_iter().enumerate() {
+        let letter = letters
+            .iter()
+            .find(|(_, pattern)| trim_columns(&pattern.pixels()) == glyph);
+
+        match letter {
+            Some((c, _)) => text.push(*c),
+            None => unknown.push(UnknownGlyph {
+                index,
+                glyph: render(&glyph),
+            }),
+        }
+    }
+
+    if unknown.is_empty() {
+        Ok(text)
+    } else {
+        Err(OcrError::UnknownGlyphs(unknown))
+    }
+}
+
+/// Splits rows of pixels into letters, separated by empty columns.
+fn split_glyphs(rows: &Pixels) -> Vec<Pixels> {
+    let width = rows.iter().map(Vec::len).max().unwrap_or_default();
+    let is_set = |x: usize, y: usize| rows[y].get(x).copied().unwrap_or_default();
+    let is_empty_column = |x: usize| (0..rows.len()).all(|y| !is_set(x, y));
+
+    let mut glyphs = vec![];
+    let mut start = None;
+
+    for x in 0..=width {
+        match (start, x == width || is_empty_column(x)) {
+            (None, false) => start = Some(x),
+            (Some(from), true) => {
+                glyphs.push(
+                    (0..rows.len())
+                        .map(|y| (from..x).map(|x| is_set(x, y)).collect())
+                        .collect(),
+                );
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    glyphs
+}
+
+/// Removes empty columns on both sides of a glyph.
+fn trim_columns(glyph: &Pixels) -> Pixels {
+    split_glyphs(glyph).into_iter().next().unwrap_or_default()
+}
+
+fn render(glyph: &Pixels) -> String {
+    glyph
+        .iter()
+        .map(|row| row.iter().map(|x| if *x { '#' } else { '.' }).collect())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::collections::{BTreeSet, HashSet};
+
+    use super::{decode, OcrError, Screen, UnknownGlyph, LARGE_LETTERS, SMALL_LETTERS};
+
+    /// Draws letters of a font next to each other, separated by `gap` empty columns.
+    fn draw(letters: &[&str], gap: usize) -> String {
+        let height = letters[0].lines().count();
+        (0..height)
+            .map(|y| {
+                letters
+                    .iter()
+                    .map(|letter| letter.lines().nth(y).unwrap())
+                    .collect::<Vec<&str>>()
+                    .join(&".".repeat(gap))
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    fn get_mock_screen() -> &'static str {
+        "#..#.###.\n#..#.#..#\n####.###.\n#..#.#..#\n#..#.#..#\n#..#.###."
+    }
+
+    #[test]
+    fn decodes_small_letters() {
+        let patterns: Vec<&str> = SMALL_LETTERS.iter().map(|(_, p)| *p).collect();
+        let expected: String = SMALL_LETTERS.iter().map(|(c, _)| c).collect();
+        assert_eq!(decode(draw(&patterns, 1).as_str()), Ok(expected));
+    }
+
+    #[test]
+    fn decodes_large_letters() {
+        let patterns: Vec<&str> = LARGE_LETTERS.iter().map(|(_, p)| *p).collect();
+        let expected: String = LARGE_LETTERS.iter().map(|(c, _)| c).collect();
+        assert_eq!(decode(draw(&patterns, 2).as_str()), Ok(expected));
+    }
+
+    #[test]
+    fn decodes_screen_output() {
+        let art = "█  █ ███  \n█  █ █  █ \n████ ███  \n█  █ █  █ \n█  █ █  █ \n█  █ ███  \n";
+        assert_eq!(decode(art), Ok("HB".into()));
+    }
+
+    #[test]
+    fn decodes_pixels() {
+        let pixels: Vec<Vec<bool>> = get_mock_screen().pixels();
+        assert_eq!(decode(&pixels), Ok("HB".into()));
+    }
+
+    #[test]
+    fn decodes_points() {
+        let points: HashSet<(i32, i32)> = get_mock_screen()
+            .lines()
+            .enumerate()
+            .flat_map(|(y, line)| {
+                line.chars()
+                    .enumerate()
+                    .filter(|(_, c)| *c == '#')
+                    .map(move |(x, _)| (x as i32 - 10, y as i32 + 3))
+            })
+            .collect();
+        assert_eq!(decode(&points), Ok("HB".into()));
+
+        let points: BTreeSet<(usize, usize)> = points
+            .iter()
+            .map(|(x, y)| ((x + 10) as usize, *y as usize))
+            .collect();
+        assert_eq!(decode(&points), Ok("HB".into()));
+    }
+
+    #[test]
+    fn reports_unknown_glyphs() {
+        let block = "####\n####\n####\n####\n####\n####";
+        let art = draw(&[SMALL_LETTERS[0].1, block, SMALL_LETTERS[1].1, block], 1);
+        let error = decode(art.as_str()).unwrap_err();
+
+        assert_eq!(
+            error,
+            OcrError::UnknownGlyphs(vec![
+                UnknownGlyph {
+                    index: 1,
+                    glyph: block.into()
+                },
+                UnknownGlyph {
+                    index: 3,
+                    glyph: block.into()
+                }
+            ])
+        );
+        assert!(error
+            .to_string()
+            .starts_with("could not recognize 2 glyph(s):\n\nglyph 1:\n####"));
+    }
+
+    #[test]
+    fn reports_unsupported_heights() {
+        assert_eq!(decode("#\n#"), Err(OcrError::UnsupportedHeight(2)));
+        assert_eq!(decode("....\n"), Err(OcrError::Empty));
+        assert_eq!(decode(&HashSet::<(u8, u8)>::new()), Err(OcrError::Empty));
+    }
+}
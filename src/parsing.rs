@@ -0,0 +1,399 @@
+//! Helpers for parsing puzzle inputs.
+//!
+//! Fixed-shape lines can be parsed with a [`Pattern`], where every `{}` captures a value:
+//!
+//! ```
+//! use advent_of_code::parsing::Pattern;
+//!
+//! let pattern = Pattern::new("{},{} -> {},{}");
+//! let line: (u32, u32, u32, u32) = pattern.parse("0,9 -> 5,9").unwrap();
+//! assert_eq!(line, (0, 9, 5, 9));
+//! ```
+
+use std::{error::Error, fmt::Display, str::FromStr};
+
+/// Extracts all integers from text, where a `-` directly before a number is a sign unless it follows a digit.
+/// Numbers that do not fit into `T` are skipped.
+pub fn integers<T: FromStr>(s: &str) -> impl Iterator<Item = T> + '_ {
+    numbers(s, true).filter_map(|number| number.parse().ok())
+}
+
+/// Extracts all integers from text, ignoring any signs, e.g. for ranges like `2-4`.
+/// Numbers that do not fit into `T` are skipped.
+pub fn unsigned_integers<T: FromStr>(s: &str) -> impl Iterator<Item = T> + '_ {
+    numbers(s, false).filter_map(|number| number.parse().ok())
+}
+
+fn numbers(s: &str, signed: bool) -> impl Iterator<Item = &str> {
+    let bytes = s.as_bytes();
+    let mut i = 0;
+
+    std::iter::from_fn(move || {
+        while i < bytes.len() && !bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+
+        if i == bytes.len() {
+            return None;
+        }
+
+        let mut start = i;
+        if signed
+            && start > 0
+            && bytes[start - 1] == b'-'
+            && (start < 2 || !bytes[start - 2].is_ascii_digit())
+        {
+            start -= 1;
+        }
+
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+
+        Some(&s[start..i])
+    })
+}
+
+/// Splits text into blocks separated by blank lines.
+pub fn blocks(input: &str) -> impl Iterator<Item = &str> {
+    let mut lines = input.lines().peekable();
+
+    std::iter::from_fn(move || {
+        while lines.next_if(|line| line.trim().is_empty()).is_some() {}
+
+        let first = lines.next()?;
+        let mut last = first;
+        while let Some(line) = lines.next_if(|line| !line.trim().is_empty()) {
+            last = line;
+        }
+
+        let start = offset_in(input, first);
+        Some(&input[start..offset_in(input, last) + last.len()])
+    })
+}
+
+/// The byte offset of a subslice within `s`.
+fn offset_in(s: &str, subslice: &str) -> usize {
+    subslice.as_ptr() as usize - s.as_ptr() as usize
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A line format where every `{}` captures a value, e.g. `"{} -> {},{}"`.
+///
+/// A capture extends up to the next occurrence of the text that follows it, or to the end of the line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pattern {
+    /// The text before the first capture.
+    prefix: String,
+    /// The text after each capture.
+    suffixes: Vec<String>,
+}
+
+/// A value captured by a [`Pattern`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Capture<'a> {
+    pub value: &'a str,
+    /// The column where the value starts, counted from 1.
+    pub column: usize,
+}
+
+impl Pattern {
+    /// # Panics
+    /// Panics if two captures follow each other without any text in between, since they could not be told apart.
+    pub fn new(pattern: &str) -> Self {
+        let mut parts = pattern.split("{}");
+        let prefix = parts.next().unwrap_or_default().to_string();
+        let suffixes: Vec<String> = parts.map(String::from).collect();
+
+        if let Some((i, _)) = suffixes
+            .iter()
+            .enumerate()
+            .find(|(i, suffix)| suffix.is_empty() && *i + 1 < suffixes.len())
+        {
+            panic!("pattern `{pattern}` has no text after capture {}.", i + 1);
+        }
+
+        Self { prefix, suffixes }
+    }
+
+    /// Matches a line against the pattern and returns the captured values.
+    pub fn captures<'a>(&self, line: &'a str) -> Result<Vec<Capture<'a>>, ParseError> {
+        if !line.starts_with(self.prefix.as_str()) {
+            return Err(ParseError::at(
+                line,
+                0,
+                ParseErrorKind::Expected(self.prefix.clone()),
+            ));
+        }
+
+        let mut rest = self.prefix.len();
+        let mut captures = vec![];
+
+        for (i, suffix) in self.suffixes.iter().enumerate() {
+            let is_last = i + 1 == self.suffixes.len();
+            let end = if suffix.is_empty() {
+                Some(line.len())
+            } else if is_last {
+                line[rest..]
+                    .ends_with(suffix.as_str())
+                    .then(|| line.len() - suffix.len())
+            } else {
+                line[rest..].find(suffix.as_str()).map(|end| rest + end)
+            };
+
+            let Some(end) = end else {
+                return Err(ParseError::at(
+                    line,
+                    rest,
+                    ParseErrorKind::Expected(suffix.clone()),
+                ));
+            };
+
+            captures.push(Capture {
+                value: &line[rest..end],
+                column: column_of(line, rest),
+            });
+            rest = end + suffix.len();
+        }
+
+        if rest < line.len() {
+            return Err(ParseError::at(line, rest, ParseErrorKind::TrailingText));
+        }
+
+        Ok(captures)
+    }
+
+    /// Matches a line against the pattern and parses the captured values into a tuple.
+    pub fn parse<T: FromCaptures>(&self, line: &str) -> Result<T, ParseError> {
+        T::from_captures(&self.captures(line)?)
+    }
+
+    /// Parses every line of the input, reporting the line number of errors.
+    pub fn parse_lines<T: FromCaptures>(&self, input: &str) -> Result<Vec<T>, ParseError> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(i, line)| self.parse(line).map_err(|err| err.on_line(i + 1)))
+            .collect()
+    }
+}
+
+impl Capture<'_> {
+    pub fn parse<T: FromStr>(&self) -> Result<T, ParseError> {
+        self.value.parse().map_err(|_| ParseError {
+            line: 1,
+            column: self.column,
+            kind: ParseErrorKind::InvalidValue {
+                value: self.value.to_string(),
+                type_name: std::any::type_name::<T>(),
+            },
+        })
+    }
+}
+
+/// Types that can be built from the values captured by a [`Pattern`], e.g. tuples of [`FromStr`] types.
+pub trait FromCaptures: Sized {
+    fn from_captures(captures: &[Capture<'_>]) -> Result<Self, ParseError>;
+}
+
+macro_rules! impl_from_captures {
+    ($len:literal: $($t:ident $i:tt),+) => {
+        impl<$($t: FromStr),+> FromCaptures for ($($t,)+) {
+            fn from_captures(captures: &[Capture<'_>]) -> Result<Self, ParseError> {
+                if captures.len() != $len {
+                    return Err(ParseError {
+                        line: 1,
+                        column: 1,
+                        kind: ParseErrorKind::CaptureCount {
+                            expected: $len,
+                            actual: captures.len(),
+                        },
+                    });
+                }
+
+                Ok(($(captures[$i].parse::<$t>()?,)+))
+            }
+        }
+    };
+}
+
+impl_from_captures!(1: A 0);
+impl_from_captures!(2: A 0, B 1);
+impl_from_captures!(3: A 0, B 1, C 2);
+impl_from_captures!(4: A 0, B 1, C 2, D 3);
+impl_from_captures!(5: A 0, B 1, C 2, D 3, E 4);
+impl_from_captures!(6: A 0, B 1, C 2, D 3, E 4, F 5);
+impl_from_captures!(7: A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_from_captures!(8: A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+
+fn column_of(line: &str, offset: usize) -> usize {
+    line[..offset].chars().count() + 1
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An error while parsing input. Lines and columns are counted from 1.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ParseErrorKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// The text of the pattern was not found.
+    Expected(String),
+    /// The line continues after the pattern.
+    TrailingText,
+    /// A captured value could not be parsed.
+    InvalidValue {
+        value: String,
+        type_name: &'static str,
+    },
+    /// The pattern captures a different number of values than requested.
+    CaptureCount { expected: usize, actual: usize },
+}
+
+impl ParseError {
+    fn at(line: &str, offset: usize, kind: ParseErrorKind) -> Self {
+        Self {
+            line: 1,
+            column: column_of(line, offset),
+            kind,
+        }
+    }
+
+    fn on_line(self, line: usize) -> Self {
+        Self { line, ..self }
+    }
+}
+
+impl Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}: ", self.line, self.column)?;
+
+        match &self.kind {
+            ParseErrorKind::Expected(text) => write!(f, "expected `{text}`."),
+            ParseErrorKind::TrailingText => write!(f, "unexpected text after the pattern."),
+            ParseErrorKind::InvalidValue { value, type_name } => {
+                write!(f, "could not parse `{value}` as {type_name}.")
+            }
+            ParseErrorKind::CaptureCount { expected, actual } => {
+                write!(
+                    f,
+                    "expected {expected} captured values, pattern has {actual}."
+                )
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{blocks, integers, unsigned_integers, ParseError, ParseErrorKind, Pattern};
+
+    #[test]
+    fn extracts_integers() {
+        let numbers: Vec<i32> = integers("x=-12, y=7..-3 (a-5)").collect();
+        assert_eq!(numbers, vec![-12, 7, -3, -5]);
+
+        let numbers: Vec<i32> = integers("1-3 12-4").collect();
+        assert_eq!(numbers, vec![1, 3, 12, 4]);
+
+        let numbers: Vec<u8> = unsigned_integers("2-4,-6 300 7").collect();
+        assert_eq!(numbers, vec![2, 4, 6, 7]);
+
+        assert_eq!(integers::<u64>("no numbers - here").count(), 0);
+    }
+
+    #[test]
+    fn splits_blocks() {
+        let input = "a\nb\n\n\nc\n  \nd\ne\n";
+        assert_eq!(blocks(input).collect::<Vec<_>>(), vec!["a\nb", "c", "d\ne"]);
+        assert_eq!(blocks("\n\n").count(), 0);
+
+        let input = "a\r\nb\r\n\r\nc\r\n";
+        assert_eq!(blocks(input).collect::<Vec<_>>(), vec!["a\r\nb", "c"]);
+    }
+
+    #[test]
+    fn parses_patterns() {
+        let pattern = Pattern::new("{} -> {},{}");
+        let (name, x, y): (String, i32, i32) = pattern.parse("a -> 1,-2").unwrap();
+        assert_eq!((name.as_str(), x, y), ("a", 1, -2));
+
+        let pattern = Pattern::new("Valve {} has flow rate={}; tunnels lead to valves {}");
+        let (valve, rate, tunnels): (String, u32, String) = pattern
+            .parse("Valve AA has flow rate=0; tunnels lead to valves DD, II, BB")
+            .unwrap();
+        assert_eq!(valve, "AA");
+        assert_eq!(rate, 0);
+        assert_eq!(tunnels, "DD, II, BB");
+
+        let pattern = Pattern::new("[{}]");
+        assert_eq!(pattern.parse::<(u8,)>("[42]"), Ok((42,)));
+    }
+
+    #[test]
+    fn parses_lines() {
+        let pattern = Pattern::new("{}-{},{}-{}");
+        let pairs: Vec<(u32, u32, u32, u32)> = pattern.parse_lines("2-4,6-8\n2-3,4-5\n").unwrap();
+        assert_eq!(pairs, vec![(2, 4, 6, 8), (2, 3, 4, 5)]);
+    }
+
+    #[test]
+    fn reports_error_positions() {
+        let pattern = Pattern::new("{} -> {}");
+        let err = pattern
+            .parse_lines::<(u32, u32)>("1 -> 2\n3 -> x\n")
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ParseError {
+                line: 2,
+                column: 6,
+                kind: ParseErrorKind::InvalidValue {
+                    value: "x".into(),
+                    type_name: "u32"
+                }
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 6: could not parse `x` as u32."
+        );
+
+        let err = pattern.parse_lines::<(u32, u32)>("1 => 2").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 1));
+        assert_eq!(err.kind, ParseErrorKind::Expected(" -> ".into()));
+
+        let pattern = Pattern::new("move {} from {}");
+        let err = pattern.parse::<(u32, u32)>("move 1 to 2").unwrap_err();
+        assert_eq!(err.column, 6);
+
+        let pattern = Pattern::new("#{}!");
+        let err = pattern.parse::<(u32,)>("#1!?").unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::Expected("!".into()));
+
+        let err = pattern.parse::<(u32, u32)>("#1!").unwrap_err();
+        assert_eq!(
+            err.kind,
+            ParseErrorKind::CaptureCount {
+                expected: 2,
+                actual: 1
+            }
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_adjacent_captures() {
+        Pattern::new("{}{}");
+    }
+}
@@ -0,0 +1,203 @@
+use std::{
+    fmt::Display,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+use crate::direction::Direction;
+
+/// A point on a plane, e.g. a position on a [`Grid`](crate::grid::Grid). `y` grows downwards.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// A point in space.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Point2 {
+    pub const ORIGIN: Self = Self::new(0, 0);
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    /// The distance when moving only orthogonally.
+    pub fn manhattan(self, other: Self) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// The distance when also moving diagonally.
+    pub fn chebyshev(self, other: Self) -> u64 {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// The neighbouring point in a direction.
+    pub fn step(self, direction: Direction) -> Self {
+        self + direction.offset()
+    }
+}
+
+impl Point3 {
+    pub const ORIGIN: Self = Self::new(0, 0, 0);
+
+    pub const fn new(x: i64, y: i64, z: i64) -> Self {
+        Self { x, y, z }
+    }
+
+    /// The distance when moving only along the axes.
+    pub fn manhattan(self, other: Self) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+
+    /// The distance when also moving diagonally.
+    pub fn chebyshev(self, other: Self) -> u64 {
+        self.x
+            .abs_diff(other.x)
+            .max(self.y.abs_diff(other.y))
+            .max(self.z.abs_diff(other.z))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+macro_rules! impl_point_ops {
+    ($point:ident, $($field:ident),+) => {
+        impl Add for $point {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self::Output {
+                Self { $($field: self.$field + rhs.$field),+ }
+            }
+        }
+
+        impl Sub for $point {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                Self { $($field: self.$field - rhs.$field),+ }
+            }
+        }
+
+        impl Mul<i64> for $point {
+            type Output = Self;
+
+            fn mul(self, rhs: i64) -> Self::Output {
+                Self { $($field: self.$field * rhs),+ }
+            }
+        }
+
+        impl Neg for $point {
+            type Output = Self;
+
+            fn neg(self) -> Self::Output {
+                Self { $($field: -self.$field),+ }
+            }
+        }
+
+        impl AddAssign for $point {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl SubAssign for $point {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+    };
+}
+
+impl_point_ops!(Point2, x, y);
+impl_point_ops!(Point3, x, y, z);
+
+impl Add<Direction> for Point2 {
+    type Output = Self;
+
+    fn add(self, rhs: Direction) -> Self::Output {
+        self.step(rhs)
+    }
+}
+
+impl AddAssign<Direction> for Point2 {
+    fn add_assign(&mut self, rhs: Direction) {
+        *self = self.step(rhs);
+    }
+}
+
+impl From<(i64, i64)> for Point2 {
+    fn from((x, y): (i64, i64)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl From<(i64, i64, i64)> for Point3 {
+    fn from((x, y, z): (i64, i64, i64)) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+impl Display for Point2 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Display for Point3 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{Point2, Point3};
+    use crate::direction::Direction;
+
+    #[test]
+    fn computes_arithmetic() {
+        let mut point = Point2::new(1, 2) + Point2::new(3, -4);
+        assert_eq!(point, Point2::new(4, -2));
+        assert_eq!(point - Point2::new(4, 0), Point2::new(0, -2));
+        assert_eq!(point * 3, Point2::new(12, -6));
+        assert_eq!(-point, Point2::new(-4, 2));
+
+        point += Point2::new(1, 1);
+        point -= Point2::new(0, 2);
+        assert_eq!(point, Point2::new(5, -3));
+
+        assert_eq!(
+            Point3::new(1, 2, 3) + Point3::new(1, 1, 1) * 2,
+            Point3::new(3, 4, 5)
+        );
+    }
+
+    #[test]
+    fn computes_distances() {
+        let (a, b) = (Point2::new(1, 1), Point2::new(-2, 5));
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+
+        let (a, b) = (Point3::ORIGIN, Point3::new(-1, 2, -3));
+        assert_eq!(a.manhattan(b), 6);
+        assert_eq!(a.chebyshev(b), 3);
+    }
+
+    #[test]
+    fn steps_in_directions() {
+        let mut point = Point2::ORIGIN + Direction::North;
+        assert_eq!(point, Point2::new(0, -1));
+
+        point += Direction::SouthEast;
+        assert_eq!(point, Point2::new(1, 0));
+        assert_eq!(point.step(Direction::West), Point2::ORIGIN);
+    }
+}
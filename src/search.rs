@@ -0,0 +1,479 @@
+//! Graph searches driven by closures, so they work on grids, state spaces and explicit graphs alike.
+//!
+//! Nodes are any `Clone + Eq + Hash` type. Unweighted searches take a `neighbours(&node)` closure returning the
+//! nodes reachable in one step, weighted searches a closure returning `(node, cost)` pairs.
+//!
+//! ```
+//! use advent_of_code::{search, Grid};
+//!
+//! let grid: Grid<char> = "S.#\n#.E".parse().unwrap();
+//! let start = grid.find(&'S').unwrap();
+//! let path = search::bfs(
+//!     start,
+//!     |&p| grid.neighbours_4(p).filter(|(_, c)| **c != '#').map(|(n, _)| n),
+//!     |&p| grid[p] == 'E',
+//! );
+//! assert_eq!(path.unwrap().cost, 3);
+//! ```
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/// A cost of an edge in a weighted search, e.g. an integer. [`Default`] is the zero cost.
+pub trait Cost: Copy + Ord + Default + Add<Output = Self> {}
+
+impl<C: Copy + Ord + Default + Add<Output = C>> Cost for C {}
+
+/// A path found by a search, from the start node to the goal node.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Path<N, C> {
+    pub nodes: Vec<N>,
+    pub cost: C,
+}
+
+/// Breadth-first search for the path with the fewest steps to a goal. The cost of the path is its number of steps.
+pub fn bfs<N, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, usize>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut parents = HashMap::new();
+    let mut seen = HashSet::from([start.clone()]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        if is_goal(&node) {
+            let nodes = reconstruct(&parents, node);
+            return Some(Path {
+                cost: nodes.len() - 1,
+                nodes,
+            });
+        }
+
+        for next in neighbours(&node) {
+            if seen.insert(next.clone()) {
+                parents.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// Breadth-first search for the number of steps to every reachable node, e.g. for flood fills.
+pub fn bfs_distances<N, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        let distance = distances[&node];
+        for next in neighbours(&node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), distance + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    distances
+}
+
+/// Depth-first search for any path to a goal. The path is not necessarily the shortest.
+pub fn dfs<N, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut parents = HashMap::new();
+    let mut seen = HashSet::new();
+    let mut stack = vec![(start, None)];
+
+    while let Some((node, parent)) = stack.pop() {
+        if !seen.insert(node.clone()) {
+            continue;
+        }
+
+        if let Some(parent) = parent {
+            parents.insert(node.clone(), parent);
+        }
+
+        if is_goal(&node) {
+            return Some(reconstruct(&parents, node));
+        }
+
+        for next in neighbours(&node) {
+            if !seen.contains(&next) {
+                stack.push((next, Some(node.clone())));
+            }
+        }
+    }
+
+    None
+}
+
+/// Dijkstra's algorithm for the cheapest path to a goal. Costs must not be negative.
+pub fn dijkstra<N, C, I>(
+    start: N,
+    neighbours: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, neighbours, |_| C::default(), is_goal)
+}
+
+/// A* search for the cheapest path to a goal. Costs must not be negative.
+///
+/// The `heuristic` estimates the remaining cost to the nearest goal. It must never overestimate,
+/// e.g. the Manhattan distance on a grid where every step costs at least 1.
+pub fn astar<N, C, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut costs = HashMap::from([(start.clone(), C::default())]);
+    let mut parents = HashMap::new();
+    let mut queue = BinaryHeap::from([Reverse((heuristic(&start), C::default(), 0))]);
+    // nodes of the queue entries, since nodes are not required to be `Ord`.
+    let mut queued = vec![start];
+
+    while let Some(Reverse((_, cost, index))) = queue.pop() {
+        let node = queued[index].clone();
+
+        if costs[&node] < cost {
+            continue;
+        }
+
+        if is_goal(&node) {
+            return Some(Path {
+                nodes: reconstruct(&parents, node),
+                cost,
+            });
+        }
+
+        for (next, step) in neighbours(&node) {
+            let next_cost = cost + step;
+            if costs.get(&next).is_none_or(|&best| next_cost < best) {
+                costs.insert(next.clone(), next_cost);
+                parents.insert(next.clone(), node.clone());
+                queue.push(Reverse((
+                    next_cost + heuristic(&next),
+                    next_cost,
+                    queued.len(),
+                )));
+                queued.push(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// Dijkstra's algorithm that keeps every cheapest path to the cheapest goals. Costs must not be negative.
+pub fn all_shortest_paths<N, C, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<ShortestPaths<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut costs = HashMap::from([(start.clone(), C::default())]);
+    let mut parents: HashMap<N, Vec<N>> = HashMap::new();
+    let mut queue = BinaryHeap::from([Reverse((C::default(), 0))]);
+    let mut queued = vec![start];
+    let mut goals = vec![];
+    let mut goal_cost = None;
+
+    while let Some(Reverse((cost, index))) = queue.pop() {
+        if goal_cost.is_some_and(|goal_cost| cost > goal_cost) {
+            break;
+        }
+
+        let node = queued[index].clone();
+
+        if costs[&node] < cost {
+            continue;
+        }
+
+        if is_goal(&node) {
+            goal_cost = Some(cost);
+            goals.push(node);
+            continue;
+        }
+
+        for (next, step) in neighbours(&node) {
+            let next_cost = cost + step;
+            match costs.get(&next) {
+                Some(&best) if next_cost > best => {}
+                Some(&best) if next_cost == best => {
+                    parents.entry(next).or_default().push(node.clone());
+                }
+                _ => {
+                    costs.insert(next.clone(), next_cost);
+                    parents.insert(next.clone(), vec![node.clone()]);
+                    queue.push(Reverse((next_cost, queued.len())));
+                    queued.push(next);
+                }
+            }
+        }
+    }
+
+    goal_cost.map(|cost| ShortestPaths {
+        cost,
+        goals,
+        parents,
+    })
+}
+
+/// Every cheapest path to the cheapest goals, found by [`all_shortest_paths`].
+#[derive(Clone, Debug)]
+pub struct ShortestPaths<N, C> {
+    pub cost: C,
+    /// The goals that were reached at the cheapest cost.
+    pub goals: Vec<N>,
+    parents: HashMap<N, Vec<N>>,
+}
+
+impl<N: Clone + Eq + Hash, C> ShortestPaths<N, C> {
+    /// The nodes that lie on any of the paths.
+    pub fn nodes(&self) -> HashSet<N> {
+        let mut nodes: HashSet<N> = self.goals.iter().cloned().collect();
+        let mut stack = self.goals.clone();
+
+        while let Some(node) = stack.pop() {
+            for parent in self.parents.get(&node).into_iter().flatten() {
+                if nodes.insert(parent.clone()) {
+                    stack.push(parent.clone());
+                }
+            }
+        }
+
+        nodes
+    }
+
+    /// Reconstructs every path from the start to a goal.
+    /// The number of paths can grow exponentially, prefer [`ShortestPaths::nodes`] when possible.
+    pub fn paths(&self) -> Vec<Vec<N>> {
+        let mut paths = vec![];
+        let mut stack: Vec<Vec<N>> = self.goals.iter().map(|goal| vec![goal.clone()]).collect();
+
+        while let Some(path) = stack.pop() {
+            match self.parents.get(path.last().unwrap()) {
+                Some(parents) => {
+                    for parent in parents {
+                        let mut path = path.clone();
+                        path.push(parent.clone());
+                        stack.push(path);
+                    }
+                }
+                None => paths.push(path.into_iter().rev().collect()),
+            }
+        }
+
+        paths
+    }
+}
+
+/// Walks the parents back from `node` to the start, which has no parent.
+fn reconstruct<N: Clone + Eq + Hash>(parents: &HashMap<N, N>, node: N) -> Vec<N> {
+    let mut nodes = vec![node];
+
+    while let Some(parent) = parents.get(nodes.last().unwrap()) {
+        nodes.push(parent.clone());
+    }
+
+    nodes.reverse();
+    nodes
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::{all_shortest_paths, astar, bfs, bfs_distances, dfs, dijkstra};
+    use crate::{direction::Direction, grid::Grid, point::Point2};
+
+    const MAZE: &str = "\
+#########
+#S..#...#
+#.#.#.#.#
+#.#...#E#
+#########";
+
+    const CAVE: &str = "\
+1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581";
+
+    const REINDEER_MAZE: &str = "\
+###############
+#.......#....E#
+#.#.###.#.###.#
+#.....#.#...#.#
+#.###.#####.#.#
+#.#.#.......#.#
+#.#.#####.###.#
+#...........#.#
+###.#.#####.#.#
+#...#.....#.#.#
+#.#.#.###.#.#.#
+#.....#...#.#.#
+#.###.#.#.#.#.#
+#S..#.....#...#
+###############";
+
+    fn open_neighbours(grid: &Grid<char>, point: Point2) -> impl Iterator<Item = Point2> + '_ {
+        grid.neighbours_4(point)
+            .filter(|(_, c)| **c != '#')
+            .map(|(p, _)| p)
+    }
+
+    #[test]
+    fn finds_fewest_steps() {
+        let grid: Grid<char> = MAZE.parse().unwrap();
+        let start = grid.find(&'S').unwrap();
+        let end = grid.find(&'E').unwrap();
+
+        let path = bfs(start, |&p| open_neighbours(&grid, p), |&p| p == end).unwrap();
+        assert_eq!(path.cost, 12);
+        assert_eq!(path.nodes.len(), 13);
+        assert_eq!(path.nodes.first(), Some(&start));
+        assert_eq!(path.nodes.last(), Some(&end));
+
+        assert_eq!(bfs(start, |&p| open_neighbours(&grid, p), |_| false), None);
+    }
+
+    #[test]
+    fn finds_distances() {
+        let grid: Grid<char> = MAZE.parse().unwrap();
+        let start = grid.find(&'S').unwrap();
+
+        let distances = bfs_distances(start, |&p| open_neighbours(&grid, p));
+        assert_eq!(distances.len(), grid.positions(|c| *c != '#').count());
+        assert_eq!(distances[&grid.find(&'E').unwrap()], 12);
+        assert_eq!(distances[&Point2::new(1, 3)], 2);
+    }
+
+    #[test]
+    fn finds_any_path() {
+        let grid: Grid<char> = MAZE.parse().unwrap();
+        let start = grid.find(&'S').unwrap();
+        let end = grid.find(&'E').unwrap();
+
+        let path = dfs(start, |&p| open_neighbours(&grid, p), |&p| p == end).unwrap();
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.last(), Some(&end));
+        assert!(path.windows(2).all(|w| w[0].manhattan(w[1]) == 1));
+
+        assert_eq!(dfs(start, |&p| open_neighbours(&grid, p), |_| false), None);
+    }
+
+    #[test]
+    fn finds_cheapest_path() {
+        let grid = Grid::parse_with(CAVE, |c| c.to_digit(10)).unwrap();
+        let end = Point2::new(9, 9);
+        let neighbours = |&p: &Point2| grid.neighbours_4(p).map(|(n, risk)| (n, *risk));
+
+        let path = dijkstra(Point2::ORIGIN, neighbours, |&p| p == end).unwrap();
+        assert_eq!(path.cost, 40);
+        assert_eq!(path.nodes.iter().skip(1).map(|&p| grid[p]).sum::<u32>(), 40);
+
+        let path = astar(
+            Point2::ORIGIN,
+            neighbours,
+            |&p| u32::try_from(p.manhattan(end)).unwrap(),
+            |&p| p == end,
+        )
+        .unwrap();
+        assert_eq!(path.cost, 40);
+    }
+
+    #[test]
+    fn finds_all_cheapest_paths() {
+        let grid: Grid<char> = REINDEER_MAZE.parse().unwrap();
+        let start = (grid.find(&'S').unwrap(), Direction::East);
+
+        let paths = all_shortest_paths(
+            start,
+            |&(p, d): &(Point2, Direction)| {
+                let forward = (grid[p.step(d)] != '#').then_some(((p.step(d), d), 1));
+                [((p, d.turn_left()), 1000), ((p, d.turn_right()), 1000)]
+                    .into_iter()
+                    .chain(forward)
+            },
+            |&(p, _)| grid[p] == 'E',
+        )
+        .unwrap();
+
+        assert_eq!(paths.cost, 7036);
+        let tiles: HashSet<Point2> = paths.nodes().into_iter().map(|(p, _)| p).collect();
+        assert_eq!(tiles.len(), 45);
+        assert_eq!(paths.paths().len(), 3);
+    }
+
+    #[test]
+    fn finds_all_paths_of_graphs() {
+        let edges = [
+            ('a', 'b', 1),
+            ('a', 'c', 1),
+            ('b', 'd', 1),
+            ('c', 'd', 1),
+            ('a', 'd', 3),
+        ];
+
+        let paths = all_shortest_paths(
+            'a',
+            |&n| {
+                edges
+                    .iter()
+                    .filter(move |(from, _, _)| *from == n)
+                    .map(|&(_, to, cost)| (to, cost))
+            },
+            |&n| n == 'd',
+        )
+        .unwrap();
+
+        assert_eq!(paths.cost, 2);
+        let mut paths = paths.paths();
+        paths.sort();
+        assert_eq!(paths, vec![vec!['a', 'b', 'd'], vec!['a', 'c', 'd']]);
+    }
+}
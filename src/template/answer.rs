@@ -0,0 +1,136 @@
+use std::fmt::Display;
+
+use crate::ocr::{self, OcrError};
+
+/// The answer of a solution part. Parts can return any type that converts into an answer, e.g. integers or strings.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+    /// Block letters drawn with `#` and `.`, e.g. on a simulated screen.
+    /// Printed as drawn, but submitted as the letters it shows.
+    Art(String),
+}
+
+impl Answer {
+    /// Converts any displayable value into a text answer, e.g. for custom types without an [`Answer`] conversion.
+    pub fn text(value: impl Display) -> Self {
+        Answer::Text(value.to_string())
+    }
+
+    /// Wraps block letters, see [`Answer::Art`].
+    pub fn art(art: impl Into<String>) -> Self {
+        Answer::Art(art.into())
+    }
+
+    /// The text that is submitted to the website, decoding the letters of art.
+    pub fn submission(&self) -> Result<String, OcrError> {
+        match self {
+            Answer::Integer(x) => Ok(x.to_string()),
+            Answer::Text(x) => Ok(x.clone()),
+            Answer::Art(x) => ocr::decode(x.as_str()),
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(x) => write!(f, "{x}"),
+            Answer::Text(x) => write!(f, "{x}"),
+            Answer::Art(x) => write!(f, "{}", x.trim_end_matches('\n')),
+        }
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::Integer(value.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(u8, u16, u32, u64, i8, i16, i32, i64, i128);
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::Integer(value as i128)
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(value: isize) -> Self {
+        Answer::Integer(value as i128)
+    }
+}
+
+impl From<u128> for Answer {
+    fn from(value: u128) -> Self {
+        i128::try_from(value).map_or_else(|_| Answer::Text(value.to_string()), Answer::Integer)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.into())
+    }
+}
+
+impl From<char> for Answer {
+    fn from(value: char) -> Self {
+        Answer::Text(value.into())
+    }
+}
+
+// other types that solutions returned before answers had to convert into an `Answer`.
+macro_rules! impl_from_display {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::text(value)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_display!(bool, f32, f64);
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::Answer;
+
+    #[test]
+    fn converts_values() {
+        assert_eq!(Answer::from(42_u32), Answer::Integer(42));
+        assert_eq!(Answer::from(-1_isize), Answer::Integer(-1));
+        assert_eq!(Answer::from(u128::MAX), Answer::Text(u128::MAX.to_string()));
+        assert_eq!(Answer::from("abc"), Answer::Text("abc".into()));
+        assert_eq!(Answer::from(true), Answer::Text("true".into()));
+        assert_eq!(Answer::from(2.5_f64), Answer::Text("2.5".into()));
+        assert_eq!(Answer::text('x'), Answer::Text("x".into()));
+    }
+
+    #[test]
+    fn submits_decoded_art() {
+        let art = "#..#.###.\n#..#.#..#\n####.###.\n#..#.#..#\n#..#.#..#\n#..#.###.\n";
+        let answer = Answer::art(art);
+        assert_eq!(answer.submission(), Ok("HB".into()));
+        assert_eq!(answer.to_string(), art.trim_end());
+        assert_eq!(Answer::from(7_u8).submission(), Ok("7".into()));
+    }
+}
@@ -0,0 +1,464 @@
+use std::{collections::HashMap, fmt::Display, fs, io::Error, path::Path, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, PuzzleId, Year};
+
+static ANSWERS_FILE_PATH: &str = "./data/answers.json";
+
+/// The outcome of submitting an answer, as reported by the Advent of Code website.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    RateLimited,
+}
+
+impl Verdict {
+    /// Derives the verdict from the message of the answer page.
+    /// Returns [`None`] for messages that do not judge the answer, e.g. when a part was already solved.
+    pub fn from_message(message: &str) -> Option<Self> {
+        if message.contains("That's the right answer") {
+            Some(Verdict::Correct)
+        } else if message.contains("You gave an answer too recently") {
+            Some(Verdict::RateLimited)
+        } else if message.contains("That's not the right answer") {
+            if message.contains("too high") {
+                Some(Verdict::TooHigh)
+            } else if message.contains("too low") {
+                Some(Verdict::TooLow)
+            } else {
+                Some(Verdict::Wrong)
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Whether the verdict rules out the submitted answer.
+    pub fn is_wrong(self) -> bool {
+        matches!(self, Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong)
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+            Verdict::Wrong => "wrong",
+            Verdict::RateLimited => "rate_limited",
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.as_str().replace('_', " "))
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "too_high" => Ok(Verdict::TooHigh),
+            "too_low" => Ok(Verdict::TooLow),
+            "wrong" => Ok(Verdict::Wrong),
+            "rate_limited" => Ok(Verdict::RateLimited),
+            s => Err(format!("unknown verdict `{s}`.")),
+        }
+    }
+}
+
+/// Reasons for refusing to submit an answer.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadyCorrect,
+    AlreadySolved(String),
+    KnownWrong(Verdict),
+    NotBelow(String),
+    NotAbove(String),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadyCorrect => write!(f, "this answer was already accepted."),
+            Refusal::AlreadySolved(answer) => {
+                write!(f, "this part was already solved with answer `{answer}`.")
+            }
+            Refusal::KnownWrong(verdict) => {
+                write!(f, "this answer was already submitted and judged {verdict}.")
+            }
+            Refusal::NotBelow(bound) => {
+                write!(
+                    f,
+                    "answer is not below `{bound}`, which was judged too high."
+                )
+            }
+            Refusal::NotAbove(bound) => {
+                write!(
+                    f,
+                    "answer is not above `{bound}`, which was judged too low."
+                )
+            }
+        }
+    }
+}
+
+/// Represents a single answer submitted for a part of a puzzle.
+#[derive(Clone, Debug)]
+pub struct Submission {
+    pub puzzle: PuzzleId,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// Represents the history of all submitted answers.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<Submission>,
+}
+
+impl Answers {
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(ANSWERS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from a JSON file. If not present, returns empty answers.
+    /// A file that can not be read or parsed is an error, so it is never overwritten with an empty history.
+    pub fn read_from_file() -> Result<Self, String> {
+        if !Path::new(ANSWERS_FILE_PATH).exists() {
+            return Ok(Answers::default());
+        }
+
+        fs::read_to_string(ANSWERS_FILE_PATH)
+            .map_err(|x| x.to_string())
+            .and_then(Answers::try_from)
+            .map_err(|e| format!("could not read \"{ANSWERS_FILE_PATH}\": {e}"))
+    }
+
+    /// Appends a submission to the history.
+    pub fn record(&mut self, puzzle: PuzzleId, part: u8, answer: &str, verdict: Verdict) {
+        self.data.push(Submission {
+            puzzle,
+            part,
+            answer: answer.into(),
+            verdict,
+        });
+    }
+
+    fn submissions(&self, puzzle: PuzzleId, part: u8) -> impl Iterator<Item = &Submission> {
+        self.data
+            .iter()
+            .filter(move |s| s.puzzle == puzzle && s.part == part)
+    }
+
+    /// Returns the accepted answer for a part, if there is one.
+    pub fn correct_answer(&self, puzzle: PuzzleId, part: u8) -> Option<&str> {
+        self.submissions(puzzle, part)
+            .find(|s| s.verdict == Verdict::Correct)
+            .map(|s| s.answer.as_str())
+    }
+
+    /// Checks an answer against the history before it is submitted.
+    /// Numeric answers are also checked against the bounds established by previous "too high" / "too low" verdicts.
+    pub fn check(&self, puzzle: PuzzleId, part: u8, answer: &str) -> Result<(), Refusal> {
+        if let Some(correct) = self.correct_answer(puzzle, part) {
+            return Err(if correct == answer {
+                Refusal::AlreadyCorrect
+            } else {
+                Refusal::AlreadySolved(correct.into())
+            });
+        }
+
+        if let Some(s) = self
+            .submissions(puzzle, part)
+            .find(|s| s.answer == answer && s.verdict.is_wrong())
+        {
+            return Err(Refusal::KnownWrong(s.verdict));
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+
+        for s in self.submissions(puzzle, part) {
+            let Ok(bound) = s.answer.parse::<i128>() else {
+                continue;
+            };
+
+            if s.verdict == Verdict::TooHigh && value >= bound {
+                return Err(Refusal::NotBelow(s.answer.clone()));
+            }
+
+            if s.verdict == Verdict::TooLow && value <= bound {
+                return Err(Refusal::NotAbove(s.answer.clone()));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "year".into(),
+            JsonValue::String(value.puzzle.year().to_string()),
+        );
+        map.insert(
+            "day".into(),
+            JsonValue::String(value.puzzle.day().to_string()),
+        );
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.as_str().into()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let year = json
+            .get("year")
+            .and_then(|v| v.get::<String>())
+            .and_then(|year| Year::from_str(year).ok())
+            .ok_or("Expected submission.year to be a Year struct.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .filter(|part| **part == 1.0 || **part == 2.0)
+            .map(|part| *part as u8)
+            .ok_or("Expected submission.part to be 1 or 2.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.verdict to be a string.")?
+            .parse()?;
+
+        Ok(Submission {
+            puzzle: PuzzleId::new(year, day),
+            part,
+            answer: answer.clone(),
+            verdict,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use crate::puzzle;
+
+    use super::{Answers, Verdict};
+
+    fn get_mock_answers() -> Answers {
+        let mut answers = Answers::default();
+        answers.record(puzzle!(2023, 1), 1, "142", Verdict::Correct);
+        answers.record(puzzle!(2023, 1), 2, "500", Verdict::TooHigh);
+        answers.record(puzzle!(2023, 1), 2, "100", Verdict::TooLow);
+        answers.record(puzzle!(2023, 1), 2, "abc", Verdict::Wrong);
+        answers.record(puzzle!(2023, 1), 2, "300", Verdict::RateLimited);
+        answers
+    }
+
+    mod verdict {
+        use super::Verdict;
+
+        #[test]
+        fn parses_messages() {
+            let cases = [
+                (
+                    "That's the right answer! You are one gold star closer.",
+                    Some(Verdict::Correct),
+                ),
+                (
+                    "That's not the right answer; your answer is too high.",
+                    Some(Verdict::TooHigh),
+                ),
+                (
+                    "That's not the right answer; your answer is too low.",
+                    Some(Verdict::TooLow),
+                ),
+                (
+                    "That's not the right answer. If you're stuck, ...",
+                    Some(Verdict::Wrong),
+                ),
+                (
+                    "You gave an answer too recently; you have 30s left to wait.",
+                    Some(Verdict::RateLimited),
+                ),
+                ("You don't seem to be solving the right level.", None),
+            ];
+
+            for (message, verdict) in cases {
+                assert_eq!(Verdict::from_message(message), verdict);
+            }
+        }
+    }
+
+    mod deserialization {
+        use crate::{puzzle, template::answers::Answers};
+
+        use super::{get_mock_answers, Verdict};
+
+        #[test]
+        fn handles_json_answers() {
+            let json = r#"{ "data": [{ "year": "2023", "day": "01", "part": 2, "answer": "42", "verdict": "too_low" }] }"#.to_string();
+            let answers = Answers::try_from(json).unwrap();
+            assert_eq!(answers.data.len(), 1);
+            let submission = answers.data.first().unwrap();
+            assert_eq!(submission.puzzle, puzzle!(2023, 1));
+            assert_eq!(submission.part, 2);
+            assert_eq!(submission.answer, "42");
+            assert_eq!(submission.verdict, Verdict::TooLow);
+        }
+
+        #[test]
+        fn round_trips_answers() {
+            let json = tinyjson::JsonValue::from(get_mock_answers())
+                .stringify()
+                .unwrap();
+            let answers = Answers::try_from(json).unwrap();
+            assert_eq!(answers.data.len(), 5);
+            assert_eq!(answers.correct_answer(puzzle!(2023, 1), 1), Some("142"));
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_unknown_verdicts() {
+            let json = r#"{ "data": [{ "year": "2023", "day": "01", "part": 1, "answer": "42", "verdict": "maybe" }] }"#.to_string();
+            Answers::try_from(json).unwrap();
+        }
+    }
+
+    mod check {
+        use crate::{puzzle, template::answers::Refusal};
+
+        use super::{get_mock_answers, Verdict};
+
+        #[test]
+        fn refuses_solved_parts() {
+            let answers = get_mock_answers();
+            assert_eq!(
+                answers.check(puzzle!(2023, 1), 1, "142"),
+                Err(Refusal::AlreadyCorrect)
+            );
+            assert_eq!(
+                answers.check(puzzle!(2023, 1), 1, "143"),
+                Err(Refusal::AlreadySolved("142".into()))
+            );
+        }
+
+        #[test]
+        fn refuses_known_wrong_answers() {
+            let answers = get_mock_answers();
+            assert_eq!(
+                answers.check(puzzle!(2023, 1), 2, "abc"),
+                Err(Refusal::KnownWrong(Verdict::Wrong))
+            );
+        }
+
+        #[test]
+        fn refuses_answers_out_of_bounds() {
+            let answers = get_mock_answers();
+            assert_eq!(
+                answers.check(puzzle!(2023, 1), 2, "500"),
+                Err(Refusal::KnownWrong(Verdict::TooHigh))
+            );
+            assert_eq!(
+                answers.check(puzzle!(2023, 1), 2, "501"),
+                Err(Refusal::NotBelow("500".into()))
+            );
+            assert_eq!(
+                answers.check(puzzle!(2023, 1), 2, "99"),
+                Err(Refusal::NotAbove("100".into()))
+            );
+        }
+
+        #[test]
+        fn allows_new_answers() {
+            let answers = get_mock_answers();
+            assert_eq!(answers.check(puzzle!(2023, 1), 2, "250"), Ok(()));
+            assert_eq!(answers.check(puzzle!(2023, 1), 2, "300"), Ok(()));
+            assert_eq!(answers.check(puzzle!(2023, 1), 2, "xyz"), Ok(()));
+            assert_eq!(answers.check(puzzle!(2023, 2), 1, "142"), Ok(()));
+        }
+    }
+}
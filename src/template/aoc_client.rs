@@ -0,0 +1,527 @@
+/// Minimal client for the Advent of Code website.
+/// Downloads inputs and puzzle descriptions and submits answers on behalf of the user.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::template::PuzzleId;
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+
+#[derive(Debug)]
+pub enum AocClientError {
+    SessionNotFound,
+    BadStatus(u16, String),
+    Transport(String),
+    UnexpectedResponse(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::SessionNotFound => write!(
+                f,
+                "no session cookie found. Set `ADVENT_OF_CODE_SESSION` or create the file `<home_directory>/.adventofcode.session`."
+            ),
+            AocClientError::BadStatus(status, body) => {
+                write!(f, "server responded with status {status}: {body}")
+            }
+            AocClientError::Transport(e) => write!(f, "could not reach server: {e}"),
+            AocClientError::UnexpectedResponse(e) => write!(f, "unexpected response: {e}"),
+            AocClientError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// Reads the session cookie from the `ADVENT_OF_CODE_SESSION` environment variable
+/// or, if not set, from the `.adventofcode.session` file in the home directory.
+pub fn read_session() -> Result<String, AocClientError> {
+    if let Ok(session) = env::var("ADVENT_OF_CODE_SESSION") {
+        if !session.trim().is_empty() {
+            return Ok(session.trim().to_string());
+        }
+    }
+
+    let home = env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .ok_or(AocClientError::SessionNotFound)?;
+
+    let session = fs::read_to_string(PathBuf::from(home).join(".adventofcode.session"))
+        .map_err(|_| AocClientError::SessionNotFound)?;
+
+    match session.trim() {
+        "" => Err(AocClientError::SessionNotFound),
+        session => Ok(session.to_string()),
+    }
+}
+
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+        }
+    }
+
+    /// Creates a client for the base URL in `AOC_BASE_URL` (defaults to the official website)
+    /// with the session returned by [`read_session`].
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        Ok(Self::new(&base_url, &read_session()?))
+    }
+
+    fn puzzle_url(&self, puzzle: PuzzleId) -> String {
+        format!(
+            "{}/{}/day/{}",
+            self.base_url,
+            puzzle.year(),
+            puzzle.day().into_inner()
+        )
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    /// Fetches the personal puzzle input.
+    pub fn get_input(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        let request = self
+            .agent
+            .get(&format!("{}/input", self.puzzle_url(puzzle)))
+            .set("Cookie", &self.cookie());
+        read_response(request.call())
+    }
+
+    /// Fetches the raw HTML of the puzzle page.
+    pub fn get_puzzle_html(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        let request = self
+            .agent
+            .get(&self.puzzle_url(puzzle))
+            .set("Cookie", &self.cookie());
+        read_response(request.call())
+    }
+
+    /// Fetches the puzzle description of all unlocked parts as markdown.
+    pub fn get_puzzle(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        let page = self.get_puzzle_html(puzzle)?;
+        let articles = html::articles(&page);
+
+        if articles.is_empty() {
+            return Err(AocClientError::UnexpectedResponse(
+                "puzzle page does not contain a description.".into(),
+            ));
+        }
+
+        Ok(articles
+            .iter()
+            .map(|article| html::to_markdown(article))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+            + "\n")
+    }
+
+    /// Submits an answer and returns the message of the response page.
+    pub fn submit(
+        &self,
+        puzzle: PuzzleId,
+        part: u8,
+        answer: &str,
+    ) -> Result<String, AocClientError> {
+        let request = self
+            .agent
+            .post(&format!("{}/answer", self.puzzle_url(puzzle)))
+            .set("Cookie", &self.cookie());
+        let page =
+            read_response(request.send_form(&[("level", &part.to_string()), ("answer", answer)]))?;
+
+        html::articles(&page)
+            .first()
+            .map(|article| html::to_markdown(article))
+            .ok_or_else(|| {
+                AocClientError::UnexpectedResponse("response does not contain a message.".into())
+            })
+    }
+}
+
+fn read_response(response: Result<ureq::Response, ureq::Error>) -> Result<String, AocClientError> {
+    match response {
+        Ok(response) => Ok(response.into_string()?),
+        Err(ureq::Error::Status(status, response)) => Err(AocClientError::BadStatus(
+            status,
+            response
+                .into_string()
+                .unwrap_or_default()
+                .trim()
+                .to_string(),
+        )),
+        Err(ureq::Error::Transport(e)) => Err(AocClientError::Transport(e.to_string())),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+pub fn read(puzzle: PuzzleId) -> Result<String, AocClientError> {
+    let client = AocClient::from_env()?;
+    let puzzle_path = get_puzzle_path(puzzle);
+
+    let description = client.get_puzzle(puzzle)?;
+    write_file(&puzzle_path, &description)?;
+
+    Ok(description)
+}
+
+pub fn download(puzzle: PuzzleId) -> Result<(), AocClientError> {
+    let client = AocClient::from_env()?;
+    let input_path = get_input_path(puzzle);
+    let puzzle_path = get_puzzle_path(puzzle);
+
+    let input = client.get_input(puzzle)?;
+    let description = client.get_puzzle(puzzle)?;
+
+    write_file(&input_path, &input)?;
+    write_file(&puzzle_path, &description)?;
+
+    println!("---");
+    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    Ok(())
+}
+
+pub fn submit(puzzle: PuzzleId, part: u8, result: &str) -> Result<String, AocClientError> {
+    AocClient::from_env()?.submit(puzzle, part, result)
+}
+
+fn get_input_path(puzzle: PuzzleId) -> String {
+    format!("data/inputs/{}/{}.txt", puzzle.year(), puzzle.day())
+}
+
+pub(crate) fn get_puzzle_path(puzzle: PuzzleId) -> String {
+    format!("data/puzzles/{}/{}.md", puzzle.year(), puzzle.day())
+}
+
+fn write_file(path: &str, contents: &str) -> Result<(), AocClientError> {
+    if let Some(dir) = Path::new(path).parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, contents)?;
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Converts the markup of the Advent of Code website into markdown.
+/// Only the small set of tags used in puzzle descriptions is supported, all other tags are dropped.
+pub mod html {
+    /// Returns the inner HTML of every `<article>` element on a page.
+    pub fn articles(page: &str) -> Vec<&str> {
+        let mut articles = vec![];
+        let mut rest = page;
+
+        while let Some(start) = rest.find("<article") {
+            let Some(open_end) = rest[start..].find('>') else {
+                break;
+            };
+            let content_start = start + open_end + 1;
+            let Some(len) = rest[content_start..].find("</article>") else {
+                break;
+            };
+            articles.push(&rest[content_start..content_start + len]);
+            rest = &rest[content_start + len..];
+        }
+
+        articles
+    }
+
+    pub fn to_markdown(html: &str) -> String {
+        let mut out = String::new();
+        let mut links: Vec<String> = vec![];
+        let mut in_pre = false;
+        let mut in_code_em = false;
+        let mut rest = html;
+
+        while !rest.is_empty() {
+            if !rest.starts_with('<') {
+                let end = rest.find('<').unwrap_or(rest.len());
+                let text = &rest[..end];
+                // whitespace between block elements would otherwise add up to extra blank lines.
+                if in_pre || !text.trim().is_empty() || !out.ends_with('\n') {
+                    out.push_str(&decode_entities(text));
+                }
+                rest = &rest[end..];
+                continue;
+            }
+
+            let Some(end) = rest.find('>') else {
+                out.push_str(&decode_entities(rest));
+                break;
+            };
+
+            let tag = &rest[1..end];
+            rest = &rest[end + 1..];
+
+            let is_closing = tag.starts_with('/');
+            let name = tag
+                .trim_start_matches('/')
+                .split(|c: char| c.is_whitespace() || c == '/')
+                .next()
+                .unwrap_or_default()
+                .to_ascii_lowercase();
+
+            match (name.as_str(), is_closing) {
+                ("h2", false) => out.push_str("## "),
+                ("h2" | "p", true) => out.push_str("\n\n"),
+                ("pre", false) => {
+                    in_pre = true;
+                    out.push_str("```\n");
+                }
+                ("pre", true) => {
+                    in_pre = false;
+                    if !out.ends_with('\n') {
+                        out.push('\n');
+                    }
+                    out.push_str("```\n\n");
+                }
+                // highlighted code is rendered as emphasized code, since markdown does not render emphasis within code.
+                ("code", false) if !in_pre && rest.starts_with("<em>") => {
+                    in_code_em = true;
+                    rest = &rest["<em>".len()..];
+                    out.push_str("*`");
+                }
+                ("em", true) if in_code_em && rest.starts_with("</code>") => {
+                    in_code_em = false;
+                    rest = &rest["</code>".len()..];
+                    out.push_str("`*");
+                }
+                ("code", _) if !in_pre => out.push('`'),
+                ("em", _) if !in_pre => out.push('*'),
+                ("a", false) => {
+                    links.push(attribute(tag, "href").unwrap_or_default());
+                    out.push('[');
+                }
+                ("a", true) => {
+                    let href = links.pop().unwrap_or_default();
+                    out.push_str(&format!("]({href})"));
+                }
+                ("li", false) => out.push_str("- "),
+                ("li", true) | ("br", _) if !out.ends_with('\n') => out.push('\n'),
+                ("ul", true) => out.push('\n'),
+                _ => {}
+            }
+        }
+
+        collapse_newlines(out.trim())
+    }
+
+    fn attribute(tag: &str, name: &str) -> Option<String> {
+        let start = tag.find(&format!("{name}=\""))? + name.len() + 2;
+        let len = tag[start..].find('"')?;
+        Some(decode_entities(&tag[start..start + len]))
+    }
+
+    fn decode_entities(s: &str) -> String {
+        s.replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&#39;", "'")
+            .replace("&apos;", "'")
+            .replace("&amp;", "&")
+    }
+
+    fn collapse_newlines(s: &str) -> String {
+        let mut out = String::with_capacity(s.len());
+        let mut newlines = 0;
+
+        for c in s.chars() {
+            if c == '\n' {
+                newlines += 1;
+                if newlines > 2 {
+                    continue;
+                }
+            } else {
+                newlines = 0;
+            }
+            out.push(c);
+        }
+
+        out
+    }
+
+    #[cfg(all(test, feature = "test_lib"))]
+    mod tests {
+        use super::{articles, to_markdown};
+
+        #[test]
+        fn extracts_articles() {
+            let page = r#"<main><article class="day-desc"><h2>a</h2></article><p>x</p><article class="day-desc"><h2 id="part2">b</h2></article></main>"#;
+            assert_eq!(
+                articles(page),
+                vec!["<h2>a</h2>", r#"<h2 id="part2">b</h2>"#]
+            );
+        }
+
+        #[test]
+        fn converts_puzzle_description() {
+            let html = concat!(
+                "<h2>--- Day 1: Trebuchet?! ---</h2><p>Something is <em>wrong</em> with <a href=\"/2023/about\">snow</a>.</p>\n",
+                "<p>For example:</p>\n",
+                "<pre><code>1abc2\npqr3stu8vwx\n</code></pre>\n",
+                "<p>The values are <code>12</code> and <code>38</code>. Together: <code><em>50</em></code>.</p>\n",
+                "<ul>\n<li>Item &lt;one&gt;</li>\n<li><em><code>two</code></em></li>\n</ul>"
+            );
+
+            let expected = [
+                "## --- Day 1: Trebuchet?! ---",
+                "",
+                "Something is *wrong* with [snow](/2023/about).",
+                "",
+                "For example:",
+                "",
+                "```",
+                "1abc2",
+                "pqr3stu8vwx",
+                "```",
+                "",
+                "The values are `12` and `38`. Together: *`50`*.",
+                "",
+                "- Item <one>",
+                "- *`two`*",
+            ]
+            .join("\n");
+
+            assert_eq!(to_markdown(html), expected);
+        }
+
+        #[test]
+        fn drops_emphasis_in_code_blocks() {
+            let html = "<pre><code>..<em>#</em>..</code></pre>";
+            assert_eq!(to_markdown(html), "```\n..#..\n```");
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{AocClient, AocClientError};
+    use crate::puzzle;
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    /// Serves a single canned response on a local port and returns the raw request it received.
+    fn mock_server(status: &'static str, body: &'static str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut request = String::new();
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(len) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut request_body = vec![0; content_length];
+            reader.read_exact(&mut request_body).unwrap();
+            request.push_str(&String::from_utf8(request_body).unwrap());
+
+            write!(
+                stream,
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+
+            request
+        });
+
+        (format!("http://{addr}"), handle)
+    }
+
+    #[test]
+    fn fetches_input_with_session() {
+        let (url, server) = mock_server("200 OK", "1abc2\npqr3stu8vwx\n");
+        let client = AocClient::new(&url, "secret");
+
+        let input = client.get_input(puzzle!(2023, 1)).unwrap();
+        let request = server.join().unwrap();
+
+        assert_eq!(input, "1abc2\npqr3stu8vwx\n");
+        assert!(request.starts_with("GET /2023/day/1/input HTTP/1.1"));
+        assert!(request.contains("Cookie: session=secret"));
+    }
+
+    #[test]
+    fn fetches_puzzle_as_markdown() {
+        let (url, server) = mock_server(
+            "200 OK",
+            "<main><article class=\"day-desc\"><h2>--- Day 8: Test ---</h2><p>Hi.</p></article></main>",
+        );
+        let client = AocClient::new(&url, "secret");
+
+        let puzzle = client.get_puzzle(puzzle!(2022, 8)).unwrap();
+        let request = server.join().unwrap();
+
+        assert_eq!(puzzle, "## --- Day 8: Test ---\n\nHi.\n");
+        assert!(request.starts_with("GET /2022/day/8 HTTP/1.1"));
+    }
+
+    #[test]
+    fn submits_answer() {
+        let (url, server) = mock_server(
+            "200 OK",
+            "<main><article><p>That's the right answer!</p></article></main>",
+        );
+        let client = AocClient::new(&url, "secret");
+
+        let message = client.submit(puzzle!(2023, 1), 2, "142").unwrap();
+        let request = server.join().unwrap();
+
+        assert_eq!(message, "That's the right answer!");
+        assert!(request.starts_with("POST /2023/day/1/answer HTTP/1.1"));
+        assert!(request.ends_with("level=2&answer=142"));
+    }
+
+    #[test]
+    fn reports_bad_status() {
+        let (url, server) = mock_server("404 Not Found", "404 Not Found");
+        let client = AocClient::new(&url, "secret");
+
+        let result = client.get_input(puzzle!(2023, 25));
+        server.join().unwrap();
+
+        assert!(matches!(result, Err(AocClientError::BadStatus(404, _))));
+    }
+}
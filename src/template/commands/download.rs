@@ -1,14 +1,21 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use crate::template::{
+    aoc_client::{self, AocClientError},
+    description, PuzzleId,
+};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+/// Downloads the input and description of a puzzle and writes its examples.
+/// Errors are returned, so `scaffold --download` and `today` can still scaffold without them.
+pub fn handle(puzzle: PuzzleId) -> Result<(), AocClientError> {
+    aoc_client::download(puzzle)?;
+
+    match description::write_examples(puzzle) {
+        Ok(paths) => {
+            for path in paths {
+                println!("🎄 Successfully wrote example to \"{path}\".");
+            }
+        }
+        Err(e) => eprintln!("failed to write examples: {e}"),
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+    Ok(())
 }
@@ -0,0 +1,67 @@
+use crate::template::history::{format_date, sparkline, History};
+use crate::template::statistics::format_nanos;
+use crate::template::{description, PuzzleId, ANSI_BOLD, ANSI_RESET};
+
+pub fn handle(puzzle: PuzzleId) {
+    let history = History::read_from_file();
+    let entries = history.for_puzzle(puzzle);
+
+    match description::title(puzzle) {
+        Some(title) => println!("{ANSI_BOLD}Day {}: {title}{ANSI_RESET}", puzzle.day()),
+        None => println!("{ANSI_BOLD}Day {}{ANSI_RESET}", puzzle.day()),
+    }
+    println!("------");
+
+    if entries.is_empty() {
+        println!(
+            "No benchmarks recorded yet. Run `cargo time {}` to record one.",
+            puzzle.day()
+        );
+        return;
+    }
+
+    for part in [1, 2] {
+        let values: Vec<f64> = entries.iter().filter_map(|e| e.part_nanos(part)).collect();
+
+        let Some(latest) = values.last() else {
+            continue;
+        };
+
+        let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+        println!(
+            "Part {part}: {} {} (min {}, max {})",
+            sparkline(&values),
+            format_nanos(*latest),
+            format_nanos(min),
+            format_nanos(max)
+        );
+    }
+
+    println!();
+    println!("| Date | Commit | rustc | Part 1 | Part 2 |");
+    println!("| :--- | :--- | :--- | :--- | :--- |");
+
+    let format_part = |nanos: Option<f64>| nanos.map_or("-".into(), format_nanos);
+
+    for entry in entries {
+        // e.g. `rustc 1.74.0 (79e9716c9 2023-11-13)` => `1.74.0`
+        let rustc = entry
+            .rustc
+            .as_deref()
+            .and_then(|x| x.split_whitespace().nth(1))
+            .unwrap_or("-");
+        let commit = entry
+            .commit
+            .as_deref()
+            .map_or("-", |x| &x[..x.len().min(7)]);
+
+        println!(
+            "| {} | {commit} | {rustc} | {} | {} |",
+            format_date(entry.timestamp),
+            format_part(entry.part_1_nanos),
+            format_part(entry.part_2_nanos)
+        );
+    }
+}
@@ -1,15 +1,22 @@
 use std::process;
 
-use crate::template::{aoc_cli, Day};
+use crate::template::{aoc_client, description::Description, PuzzleId};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
-
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
+pub fn handle(puzzle: PuzzleId) {
+    let description = match aoc_client::read(puzzle) {
+        Ok(markdown) => Description::parse(&markdown),
+        Err(e) => match Description::read(puzzle) {
+            // fall back to the downloaded description, e.g. when offline.
+            Some(description) => {
+                eprintln!("failed to fetch puzzle, showing the downloaded description: {e}");
+                description
+            }
+            None => {
+                eprintln!("failed to read puzzle: {e}");
+                process::exit(1);
+            }
+        },
     };
+
+    print!("{}", description.render());
 }
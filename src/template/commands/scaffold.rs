@@ -1,14 +1,95 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
     process,
 };
 
-use crate::template::Day;
+use crate::template::{
+    description::{self, Description},
+    PuzzleId,
+};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
+/// Directory of user-defined module templates, selected with `--template <name>`.
+const TEMPLATES_DIR: &str = "templates";
+
+const DEFAULT_ANSWER_TYPE: &str = "u32";
+
+/// Values of the placeholders of a module template, besides the ones derived from the puzzle.
+pub struct TemplateValues {
+    /// Title of the puzzle, if its description was downloaded.
+    pub title: Option<String>,
+    /// Return type of the parts, wrapped in an `Option`.
+    pub answer_type: String,
+    /// Expected answers for the examples of part one and two, as Rust expressions.
+    pub example_answers: [Option<String>; 2],
+    /// Whether the example of part two is stored separately in `NN-2.txt`.
+    pub has_part_two_example: bool,
+}
+
+impl TemplateValues {
+    /// Fills in the values from the downloaded puzzle description, if there is one.
+    fn new(puzzle: PuzzleId, answer_type: Option<&str>) -> Self {
+        let description = Description::read(puzzle);
+        let answers = description
+            .as_ref()
+            .map_or([None, None], Description::example_answers);
+        let answer_type =
+            answer_type.map_or_else(|| infer_answer_type(&answers).to_string(), str::to_string);
+
+        let example_answers =
+            answers.map(|answer| answer.map(|answer| format_answer(&answer, &answer_type)));
+
+        Self {
+            title: description.as_ref().and_then(|d| d.title.clone()),
+            answer_type,
+            example_answers,
+            has_part_two_example: description.is_some_and(|d| d.examples().len() > 1),
+        }
+    }
+}
+
+/// The smallest of the usual answer types that fits all example answers, e.g. `i64` if one is negative.
+fn infer_answer_type(answers: &[Option<String>; 2]) -> &'static str {
+    let answers: Vec<&String> = answers.iter().flatten().collect();
+    let fits = |parse: fn(&str) -> bool| answers.iter().all(|answer| parse(answer));
+
+    if fits(|a| a.parse::<u32>().is_ok()) {
+        DEFAULT_ANSWER_TYPE
+    } else if fits(|a| a.parse::<i64>().is_ok()) {
+        "i64"
+    } else if fits(|a| a.parse::<u64>().is_ok()) {
+        "u64"
+    } else {
+        "String"
+    }
+}
+
+/// Formats an example answer as an expression of the answer type, e.g. `142` or `"CMZ".to_string()`.
+fn format_answer(answer: &str, answer_type: &str) -> String {
+    if answer_type != "String" && answer.parse::<i128>().is_ok() {
+        answer.to_string()
+    } else {
+        format!("{answer:?}.to_string()")
+    }
+}
+
+/// Reads the module template `templates/<name>.txt`.
+/// Without a name, `templates/default.txt` is used if it exists, and the embedded template otherwise.
+fn read_template(name: Option<&str>) -> Result<String, String> {
+    match name {
+        Some(name) => {
+            let path = format!("{TEMPLATES_DIR}/{name}.txt");
+            fs::read_to_string(&path)
+                .map_err(|e| format!("could not read template \"{path}\": {e}"))
+        }
+        None => Ok(fs::read_to_string(format!("{TEMPLATES_DIR}/default.txt"))
+            .unwrap_or_else(|_| MODULE_TEMPLATE.to_string())),
+    }
+}
+
 fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
     if overwrite {
@@ -20,13 +101,87 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+}
+
+/// Fills in the placeholders of a module template:
+/// `%YEAR%`, `%DAY%` (padded, e.g. `01`), `%DAY_NUMBER%` (e.g. `1`), `%TITLE%`, `%ANSWER_TYPE%`,
+/// `%PART_ONE_EXAMPLE%` and `%PART_TWO_EXAMPLE%` (e.g. `Some(142)` or `None`), `%PART_TWO_EXAMPLE_INPUT%`
+/// (reads the example file of part two) and `%PARTS%` (the part argument of `solution!`).
+/// Lines between `%PART_TWO_START%` and `%PART_TWO_END%` are only kept for puzzles with a second part.
+fn render_module(template: &str, puzzle: PuzzleId, values: &TemplateValues) -> String {
+    let has_part_two = puzzle.part_count() > 1;
+    let mut keep = true;
+    let mut lines = vec![];
+
+    for line in template.lines() {
+        match line {
+            "%PART_TWO_START%" => keep = has_part_two,
+            "%PART_TWO_END%" => keep = true,
+            _ if keep => lines.push(line),
+            _ => {}
+        }
+    }
+
+    let parts = if has_part_two { "" } else { ", 1" };
+    let [part_one_example, part_two_example] = values
+        .example_answers
+        .clone()
+        .map(|answer| answer.map_or_else(|| "None".into(), |answer| format!("Some({answer})")));
+    let part_two_example_input = if values.has_part_two_example {
+        r#"advent_of_code::template::read_file_part("examples", PUZZLE, 2)"#
+    } else {
+        r#"advent_of_code::template::read_file("examples", PUZZLE)"#
+    };
+
+    (lines.join("\n") + "\n")
+        .replace("%YEAR%", &puzzle.year().to_string())
+        .replace("%DAY_NUMBER%", &puzzle.day().into_inner().to_string())
+        .replace("%DAY%", &puzzle.day().to_string())
+        .replace("%TITLE%", values.title.as_deref().unwrap_or_default())
+        .replace("%ANSWER_TYPE%", &values.answer_type)
+        .replace("%PART_ONE_EXAMPLE%", &part_one_example)
+        .replace("%PART_TWO_EXAMPLE_INPUT%", part_two_example_input)
+        .replace("%PART_TWO_EXAMPLE%", &part_two_example)
+        .replace("%PARTS%", parts)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+pub fn handle(
+    puzzle: PuzzleId,
+    overwrite: bool,
+    template: Option<&str>,
+    answer_type: Option<&str>,
+) {
+    let year = puzzle.year();
+    let day = puzzle.day();
+
+    let input_path = format!("data/inputs/{year}/{day}.txt");
+    let example_path = format!("data/examples/{year}/{day}.txt");
+    let module_path = format!("src/bin/{puzzle}.rs");
+
+    for dir in [
+        format!("data/inputs/{year}"),
+        format!("data/examples/{year}"),
+    ] {
+        if let Err(e) = fs::create_dir_all(&dir) {
+            eprintln!("Failed to create directory \"{dir}\": {e}");
+            process::exit(1);
+        }
+    }
+
+    let template = match read_template(template) {
+        Ok(template) => template,
+        Err(e) => {
+            eprintln!("Failed to read template: {e}");
+            process::exit(1);
+        }
+    };
+
+    let values = TemplateValues::new(puzzle, answer_type);
 
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
@@ -36,11 +191,7 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    match file.write_all(render_module(&template, puzzle, &values).as_bytes()) {
         Ok(()) => {
             println!("Created module file \"{}\"", &module_path);
         }
@@ -51,19 +202,33 @@ pub fn handle(day: Day, overwrite: bool) {
     }
 
     match create_file(&input_path) {
-        Ok(_) => {
+        Ok(_) if fs::metadata(&input_path).is_ok_and(|m| m.len() == 0) => {
             println!("Created empty input file \"{}\"", &input_path);
         }
+        Ok(_) => {}
         Err(e) => {
             eprintln!("Failed to create input file: {e}");
             process::exit(1);
         }
     }
 
+    match description::write_examples(puzzle) {
+        Ok(paths) => {
+            for path in paths {
+                println!("Created example file \"{path}\"");
+            }
+        }
+        Err(e) => {
+            eprintln!("Failed to write example file: {e}");
+            process::exit(1);
+        }
+    }
+
     match create_file(&example_path) {
-        Ok(_) => {
+        Ok(_) if fs::metadata(&example_path).is_ok_and(|m| m.len() == 0) => {
             println!("Created empty example file \"{}\"", &example_path);
         }
+        Ok(_) => {}
         Err(e) => {
             eprintln!("Failed to create example file: {e}");
             process::exit(1);
@@ -71,5 +236,111 @@ pub fn handle(day: Day, overwrite: bool) {
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    println!("🎄 Type `cargo solve {day} --year {year}` to run your solution.");
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{
+        format_answer, infer_answer_type, render_module, TemplateValues, DEFAULT_ANSWER_TYPE,
+        MODULE_TEMPLATE,
+    };
+    use crate::puzzle;
+
+    fn get_mock_values() -> TemplateValues {
+        TemplateValues {
+            title: None,
+            answer_type: DEFAULT_ANSWER_TYPE.into(),
+            example_answers: [None, None],
+            has_part_two_example: false,
+        }
+    }
+
+    #[test]
+    fn renders_both_parts() {
+        let module = render_module(MODULE_TEMPLATE, puzzle!(2023, 1), &get_mock_values());
+        assert!(module.starts_with("advent_of_code::solution!(2023, 1);\n"));
+        assert!(module.contains("pub fn part_one(input: &str) -> Option<u32>"));
+        assert!(module.contains("pub fn part_two"));
+        assert!(module.contains("fn test_part_two"));
+        assert!(module.contains("assert_eq!(result, None);"));
+        assert!(
+            module.contains("part_two(&advent_of_code::template::read_file(\"examples\", PUZZLE))")
+        );
+        assert!(!module.contains('%'));
+    }
+
+    #[test]
+    fn renders_single_part() {
+        let module = render_module(MODULE_TEMPLATE, puzzle!(2023, 25), &get_mock_values());
+        assert!(module.starts_with("advent_of_code::solution!(2023, 25, 1);\n"));
+        assert!(!module.contains("part_two"));
+        assert!(module.contains("fn test_part_one"));
+        assert!(!module.contains('%'));
+
+        let module = render_module(MODULE_TEMPLATE, puzzle!(2025, 12), &get_mock_values());
+        assert!(module.starts_with("advent_of_code::solution!(2025, 12, 1);\n"));
+        assert!(!module.contains("part_two"));
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        let template = "// %YEAR% day %DAY% (%DAY_NUMBER%): %TITLE%\n%ANSWER_TYPE% %PART_ONE_EXAMPLE% %PART_TWO_EXAMPLE%";
+        let values = TemplateValues {
+            title: Some("Trebuchet?!".into()),
+            answer_type: "u64".into(),
+            example_answers: [Some("142".into()), None],
+            has_part_two_example: false,
+        };
+
+        assert_eq!(
+            render_module(template, puzzle!(2023, 1), &values),
+            "// 2023 day 01 (1): Trebuchet?!\nu64 Some(142) None\n"
+        );
+    }
+
+    #[test]
+    fn renders_examples() {
+        let values = TemplateValues {
+            title: None,
+            answer_type: "String".into(),
+            example_answers: [
+                Some("\"CMZ\".to_string()".into()),
+                Some("\"MCD\".to_string()".into()),
+            ],
+            has_part_two_example: true,
+        };
+
+        let module = render_module(MODULE_TEMPLATE, puzzle!(2022, 5), &values);
+        assert!(module.contains("pub fn part_one(input: &str) -> Option<String>"));
+        assert!(module.contains("assert_eq!(result, Some(\"CMZ\".to_string()));"));
+        assert!(module.contains("assert_eq!(result, Some(\"MCD\".to_string()));"));
+        assert!(module.contains(
+            "part_two(&advent_of_code::template::read_file_part(\"examples\", PUZZLE, 2))"
+        ));
+    }
+
+    #[test]
+    fn infers_answer_types() {
+        let answers = |a: &str, b: &str| [Some(a.to_string()), Some(b.to_string())];
+        assert_eq!(infer_answer_type(&[None, None]), "u32");
+        assert_eq!(infer_answer_type(&answers("142", "281")), "u32");
+        assert_eq!(infer_answer_type(&answers("142", "-3")), "i64");
+        assert_eq!(infer_answer_type(&answers("142", "5000000000")), "i64");
+        assert_eq!(
+            infer_answer_type(&answers("142", "18446744073709551615")),
+            "u64"
+        );
+        assert_eq!(
+            infer_answer_type(&answers("-3", "18446744073709551615")),
+            "String"
+        );
+        assert_eq!(infer_answer_type(&answers("142", "CMZ")), "String");
+
+        assert_eq!(format_answer("-3", "i64"), "-3");
+        assert_eq!(format_answer("142", "String"), "\"142\".to_string()");
+        assert_eq!(format_answer("CMZ", "String"), "\"CMZ\".to_string()");
+    }
 }
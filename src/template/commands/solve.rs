@@ -1,9 +1,16 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::PuzzleId;
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+pub fn handle(puzzle: PuzzleId, release: bool, dhat: bool, submit_part: Option<u8>) {
+    if let Some(part) = submit_part {
+        if part == 0 || part > puzzle.part_count() {
+            eprintln!("Cannot submit part {part}: puzzle {puzzle} has no such part.");
+            process::exit(1);
+        }
+    }
+
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.to_string()];
 
     if dhat {
         cmd_args.extend([
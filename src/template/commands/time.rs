@@ -1,27 +1,59 @@
 use std::collections::HashSet;
+use std::process;
 
+use crate::template::history::History;
+use crate::template::report::PARSE_PART;
 use crate::template::run_multi::run_multi;
-use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::statistics::format_nanos;
+use crate::template::timings::{PartComparison, Timings};
+use crate::template::{all_days, readme_benchmarks, Day, PuzzleId, Year, ANSI_BOLD, ANSI_RESET};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+/// Default slowdown in percent that is tolerated by `--compare` before a part counts as a regression.
+pub const DEFAULT_REGRESSION_THRESHOLD: f64 = 10.0;
+
+pub fn handle(
+    year: Year,
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    compare: bool,
+    threshold: f64,
+) {
     let stored_timings = Timings::read_from_file();
 
     let days_to_run = day.map_or_else(
         || {
             if run_all {
                 all_days().collect()
+            } else if compare {
+                // when comparing, re-bench the days that have stored timings.
+                all_days()
+                    .filter(|day| {
+                        let puzzle = PuzzleId::new(year, *day);
+                        stored_timings.data.iter().any(|t| t.puzzle == puzzle)
+                    })
+                    .collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
                 all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
+                    .filter(|day| !stored_timings.is_day_complete(PuzzleId::new(year, *day)))
                     .collect()
             }
         },
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let timings = run_multi(year, &days_to_run, true, true, 1).unwrap();
+
+    if let Err(e) = History::append(&timings) {
+        eprintln!("Failed to record benchmark history: {e}");
+    }
+
+    let regressions = if compare {
+        print_comparison(&stored_timings.compare(&timings), threshold)
+    } else {
+        0
+    };
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
@@ -32,9 +64,58 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
+            Err(e) => {
+                eprintln!("Failed to store updated benchmarks: {e}");
             }
         }
     }
+
+    if regressions > 0 {
+        process::exit(1);
+    }
+}
+
+/// Prints the change of every compared part and returns the number of regressions.
+fn print_comparison(comparisons: &[PartComparison], threshold: f64) -> usize {
+    println!();
+    println!("{ANSI_BOLD}Comparison with stored benchmarks{ANSI_RESET}");
+    println!("------");
+
+    if comparisons.is_empty() {
+        println!("No stored benchmarks with statistics to compare against.");
+        return 0;
+    }
+
+    let mut regressions = 0;
+
+    for comparison in comparisons {
+        let change = comparison.change_percent();
+        let is_regression = comparison.is_regression(threshold);
+
+        if is_regression {
+            regressions += 1;
+        }
+
+        let part = if comparison.part == PARSE_PART {
+            "Parse".into()
+        } else {
+            format!("Part {}", comparison.part)
+        };
+
+        println!(
+            "Day {}, {part}: {} → {} ({change:+.1}%, {}){}",
+            comparison.puzzle.day(),
+            format_nanos(comparison.baseline_nanos),
+            format_nanos(comparison.current_nanos),
+            if change > 0.0 { "slower" } else { "faster" },
+            if is_regression { " ✖" } else { "" }
+        );
+    }
+
+    if regressions > 0 {
+        println!();
+        println!("{regressions} part(s) regressed by more than {threshold}%.");
+    }
+
+    regressions
 }
@@ -0,0 +1,132 @@
+use std::process;
+
+use crate::template::answers::Answers;
+use crate::template::run_multi::child_commands;
+use crate::template::{all_days, Day, PuzzleId, Year, ANSI_BOLD, ANSI_RESET};
+
+/// Result of comparing the output of a part with its accepted answer.
+enum Check {
+    Pass,
+    Fail {
+        expected: String,
+        actual: Option<String>,
+    },
+    Unknown,
+    /// The solution could not be run.
+    Error,
+}
+
+impl Check {
+    fn symbol(&self) -> &'static str {
+        match self {
+            Check::Pass => "✔",
+            Check::Fail { .. } | Check::Error => "✖",
+            Check::Unknown => "-",
+        }
+    }
+}
+
+pub fn handle(year: Year, day: Option<Day>, run_all: bool, is_release: bool) {
+    let answers = match Answers::read_from_file() {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("failed to read answers: {e}");
+            process::exit(1);
+        }
+    };
+
+    let has_answers = |day: &Day| {
+        let puzzle = PuzzleId::new(year, *day);
+        (1..=2).any(|part| answers.correct_answer(puzzle, part).is_some())
+    };
+
+    // when the `--all` flag is not set, skip days that do not have an accepted answer yet.
+    let days_to_run: Vec<Day> = match day {
+        Some(day) => vec![day],
+        None => all_days()
+            .filter(|day| run_all || has_answers(day))
+            .collect(),
+    };
+
+    let mut rows: Vec<(Day, [Check; 2])> = Vec::with_capacity(days_to_run.len());
+
+    for (i, day) in days_to_run.into_iter().enumerate() {
+        let puzzle = PuzzleId::new(year, day);
+
+        if i > 0 {
+            println!();
+        }
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        let reports = match child_commands::run_solution(puzzle, false, is_release) {
+            Ok(reports) => reports,
+            Err(e) => {
+                eprintln!("failed to run solution: {e}");
+                rows.push((day, [Check::Error, Check::Error]));
+                continue;
+            }
+        };
+
+        if reports.is_empty() {
+            println!("Not solved.");
+        }
+
+        let checks = [1, 2].map(|part| {
+            let actual = child_commands::answer_for(&reports, part).map(String::from);
+            match answers.correct_answer(puzzle, part) {
+                None => Check::Unknown,
+                Some(expected) if actual.as_deref() == Some(expected) => Check::Pass,
+                Some(expected) => Check::Fail {
+                    expected: expected.into(),
+                    actual,
+                },
+            }
+        });
+
+        rows.push((day, checks));
+    }
+
+    println!();
+    println!("{ANSI_BOLD}| Day | Part 1 | Part 2 |{ANSI_RESET}");
+
+    for (day, [part_1, part_2]) in &rows {
+        println!(
+            "|  {day} |   {}    |   {}    |",
+            part_1.symbol(),
+            part_2.symbol()
+        );
+    }
+
+    let mut failed = false;
+
+    for (day, checks) in &rows {
+        if matches!(checks, [Check::Error, _]) {
+            if !failed {
+                println!();
+            }
+            failed = true;
+            println!("Day {day}: could not run the solution.");
+            continue;
+        }
+
+        for (part, check) in checks.iter().enumerate() {
+            if let Check::Fail { expected, actual } = check {
+                if !failed {
+                    println!();
+                }
+                failed = true;
+                println!(
+                    "Day {day}, Part {}: expected `{expected}`, got `{}`.",
+                    part + 1,
+                    actual.as_deref().unwrap_or("✖")
+                );
+            }
+        }
+    }
+
+    if failed {
+        process::exit(1);
+    }
+}
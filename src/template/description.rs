@@ -0,0 +1,365 @@
+/// Parses puzzle descriptions downloaded by `download` and renders them in the terminal.
+use std::{fs, io};
+
+use crate::template::{aoc_client, PuzzleId, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// A puzzle description, as written to `data/puzzles` by [`aoc_client::download`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Description {
+    /// Title of the puzzle, e.g. `Trebuchet?!`.
+    pub title: Option<String>,
+    /// The unlocked parts, each starting with a `## ---` heading.
+    pub parts: Vec<Part>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Part {
+    /// The heading without the leading `## `, e.g. `--- Part Two ---`.
+    pub heading: String,
+    /// The markdown below the heading.
+    pub body: String,
+}
+
+impl Description {
+    pub fn parse(markdown: &str) -> Self {
+        let mut parts: Vec<Part> = vec![];
+
+        for line in markdown.lines() {
+            match (line.strip_prefix("## "), parts.last_mut()) {
+                (Some(heading), _) => parts.push(Part {
+                    heading: heading.trim().to_string(),
+                    body: String::new(),
+                }),
+                (None, Some(part)) => {
+                    part.body.push_str(line);
+                    part.body.push('\n');
+                }
+                (None, None) => {}
+            }
+        }
+
+        for part in &mut parts {
+            part.body = part.body.trim_matches('\n').to_string();
+        }
+
+        let title = parts.first().and_then(|part| {
+            let (_, title) = part.heading.split_once(": ")?;
+            Some(title.trim_end_matches('-').trim().to_string())
+        });
+
+        Self { title, parts }
+    }
+
+    /// Reads and parses the downloaded description of a puzzle, if there is one.
+    pub fn read(puzzle: PuzzleId) -> Option<Self> {
+        fs::read_to_string(aoc_client::get_puzzle_path(puzzle))
+            .ok()
+            .map(|markdown| Self::parse(&markdown))
+    }
+
+    /// The example inputs: the first code block, and the first code block of part two if it is different.
+    pub fn examples(&self) -> Vec<String> {
+        let mut examples: Vec<String> = self
+            .parts
+            .first()
+            .and_then(|part| part.code_blocks().into_iter().next())
+            .into_iter()
+            .collect();
+
+        if let Some(example) = self
+            .parts
+            .get(1)
+            .and_then(|part| part.code_blocks().into_iter().next())
+        {
+            if examples.first() != Some(&example) {
+                examples.push(example);
+            }
+        }
+
+        examples
+    }
+
+    /// The answers to the examples of part one and two, as far as they are unlocked.
+    pub fn example_answers(&self) -> [Option<String>; 2] {
+        [0, 1].map(|i| self.parts.get(i).and_then(Part::example_answer))
+    }
+
+    /// Renders the description for the terminal: headings and emphasized text are bold, inline code is italic
+    /// and code blocks are indented.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+
+        for part in &self.parts {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            out.push_str(&format!("{ANSI_BOLD}{}{ANSI_RESET}\n\n", part.heading));
+
+            let mut in_code_block = false;
+            for line in part.body.lines() {
+                if line.starts_with("```") {
+                    in_code_block = !in_code_block;
+                } else if in_code_block {
+                    out.push_str(&format!("    {line}\n"));
+                } else {
+                    out.push_str(&render_inline(line));
+                    out.push('\n');
+                }
+            }
+        }
+
+        out
+    }
+}
+
+impl Part {
+    /// The contents of the fenced code blocks, each ending with a newline.
+    pub fn code_blocks(&self) -> Vec<String> {
+        let mut blocks = vec![];
+        let mut current: Option<String> = None;
+
+        for line in self.body.lines() {
+            match (line.starts_with("```"), current.as_mut()) {
+                (true, None) => current = Some(String::new()),
+                (true, Some(_)) => blocks.extend(current.take()),
+                (false, Some(block)) => {
+                    block.push_str(line);
+                    block.push('\n');
+                }
+                (false, None) => {}
+            }
+        }
+
+        blocks
+    }
+
+    /// The emphasized code outside of code blocks, e.g. `142` for *`142`*.
+    pub fn answers(&self) -> Vec<String> {
+        let mut answers = vec![];
+        let mut in_code_block = false;
+
+        for line in self.body.lines() {
+            if line.starts_with("```") {
+                in_code_block = !in_code_block;
+                continue;
+            }
+            if in_code_block {
+                continue;
+            }
+
+            let mut rest = line;
+            while let Some(start) = rest.find("*`") {
+                let Some(len) = rest[start + 2..].find("`*") else {
+                    break;
+                };
+                answers.push(rest[start + 2..start + 2 + len].to_string());
+                rest = &rest[start + 2 + len + 2..];
+            }
+        }
+
+        answers
+    }
+
+    /// The last emphasized code, which usually is the answer to the example.
+    pub fn example_answer(&self) -> Option<String> {
+        self.answers().pop()
+    }
+}
+
+/// Reads the title of a downloaded puzzle, e.g. `Trebuchet?!`.
+pub fn title(puzzle: PuzzleId) -> Option<String> {
+    Description::read(puzzle)?.title
+}
+
+/// Writes the examples of a downloaded description to `data/examples`, where the second example goes into `NN-2.txt`.
+/// Files that already have content are kept. Returns the paths of the written files.
+pub fn write_examples(puzzle: PuzzleId) -> Result<Vec<String>, io::Error> {
+    let Some(description) = Description::read(puzzle) else {
+        return Ok(vec![]);
+    };
+
+    let year = puzzle.year();
+    let day = puzzle.day();
+    let mut written = vec![];
+
+    for (i, example) in description.examples().iter().enumerate() {
+        let path = match i {
+            0 => format!("data/examples/{year}/{day}.txt"),
+            _ => format!("data/examples/{year}/{day}-{}.txt", i + 1),
+        };
+
+        let is_empty = fs::read_to_string(&path).map_or(true, |s| s.trim().is_empty());
+        if is_empty {
+            fs::create_dir_all(format!("data/examples/{year}"))?;
+            fs::write(&path, example)?;
+            written.push(path);
+        }
+    }
+
+    Ok(written)
+}
+
+/// Renders the markdown of a line of text: `*emphasis*`, `` `code` ``, *`emphasized code`* and `[links](...)`,
+/// of which only the text is kept.
+fn render_inline(line: &str) -> String {
+    let mut out = String::new();
+    let mut rest = line;
+
+    while let Some(c) = rest.chars().next() {
+        let styled = match c {
+            '*' if rest.starts_with("*`") => delimited(rest, "*`", "`*")
+                .map(|(text, len)| (format!("{ANSI_BOLD}{text}{ANSI_RESET}"), len)),
+            // like in markdown, emphasis can not start or end with whitespace, e.g. in `2 * 3 * 4`.
+            '*' => delimited(rest, "*", "*")
+                .filter(|(text, _)| text.trim() == *text)
+                .map(|(text, len)| {
+                    (
+                        format!("{ANSI_BOLD}{}{ANSI_RESET}", render_inline(text)),
+                        len,
+                    )
+                }),
+            '`' => delimited(rest, "`", "`")
+                .map(|(text, len)| (format!("{ANSI_ITALIC}{text}{ANSI_RESET}"), len)),
+            '[' => delimited(rest, "[", "]").and_then(|(text, len)| {
+                let (_, href_len) = delimited(&rest[len..], "(", ")")?;
+                Some((render_inline(text), len + href_len))
+            }),
+            _ => None,
+        };
+
+        match styled {
+            Some((text, len)) => {
+                out.push_str(&text);
+                rest = &rest[len..];
+            }
+            None => {
+                out.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+
+    out
+}
+
+/// Splits off text enclosed in `open` and `close` at the start of `s`. Returns the text and the length of the match.
+fn delimited<'a>(s: &'a str, open: &str, close: &str) -> Option<(&'a str, usize)> {
+    let inner = s.strip_prefix(open)?;
+    let len = inner.find(close).filter(|&len| len > 0)?;
+    Some((&inner[..len], open.len() + len + close.len()))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{render_inline, Description, Part};
+    use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+    const DESCRIPTION: &str = "## --- Day 1: Trebuchet?! ---
+
+Something is wrong with [global snow production](/2023/about).
+
+For example:
+
+```
+1abc2
+pqr3stu8vwx
+```
+
+In this example, the calibration values are `12` and `38`. Adding these together produces *`142`*.
+
+## --- Part Two ---
+
+Some of the digits are actually *spelled out with letters*.
+
+```
+two1nine
+eightwothree
+```
+
+Adding these together produces *`281`*.
+
+What is the sum of all of the calibration values?
+";
+
+    fn part(body: &str) -> Part {
+        Part {
+            heading: "--- Part Two ---".into(),
+            body: body.into(),
+        }
+    }
+
+    #[test]
+    fn parses_description() {
+        let description = Description::parse(DESCRIPTION);
+        assert_eq!(description.title.as_deref(), Some("Trebuchet?!"));
+        assert_eq!(description.parts.len(), 2);
+        assert_eq!(description.parts[0].heading, "--- Day 1: Trebuchet?! ---");
+        assert!(description.parts[0].body.starts_with("Something is wrong"));
+        assert!(description.parts[0].body.ends_with("*`142`*."));
+        assert_eq!(description.parts[1].heading, "--- Part Two ---");
+
+        assert_eq!(Description::parse("no headings").parts, vec![]);
+        assert_eq!(Description::parse("no headings").title, None);
+    }
+
+    #[test]
+    fn extracts_code_blocks_and_answers() {
+        let description = Description::parse(DESCRIPTION);
+        assert_eq!(
+            description.parts[0].code_blocks(),
+            vec!["1abc2\npqr3stu8vwx\n"]
+        );
+        assert_eq!(description.parts[0].answers(), vec!["142"]);
+        assert_eq!(
+            part("*`a`* and *`b`*, but not\n```\n*`c`*\n```").answers(),
+            vec!["a", "b"]
+        );
+        assert_eq!(part("no *emphasis*").example_answer(), None);
+    }
+
+    #[test]
+    fn extracts_examples() {
+        let description = Description::parse(DESCRIPTION);
+        assert_eq!(
+            description.examples(),
+            vec!["1abc2\npqr3stu8vwx\n", "two1nine\neightwothree\n"]
+        );
+        assert_eq!(
+            description.example_answers(),
+            [Some("142".into()), Some("281".into())]
+        );
+
+        let part_one = DESCRIPTION.split("## --- Part Two").next().unwrap();
+        let description = Description::parse(part_one);
+        assert_eq!(description.examples(), vec!["1abc2\npqr3stu8vwx\n"]);
+        assert_eq!(description.example_answers(), [Some("142".into()), None]);
+
+        let same_example =
+            format!("{part_one}## --- Part Two ---\n\n```\n1abc2\npqr3stu8vwx\n```\n");
+        assert_eq!(Description::parse(&same_example).examples().len(), 1);
+    }
+
+    #[test]
+    fn renders_inline_markdown() {
+        assert_eq!(
+            render_inline("a *b* `c` *`d`* [e *f*](/g) h"),
+            format!(
+                "a {ANSI_BOLD}b{ANSI_RESET} {ANSI_ITALIC}c{ANSI_RESET} {ANSI_BOLD}d{ANSI_RESET} e {ANSI_BOLD}f{ANSI_RESET} h"
+            )
+        );
+        assert_eq!(render_inline("2 * 3 [x] **"), "2 * 3 [x] **");
+    }
+
+    #[test]
+    fn renders_description() {
+        let rendered = Description::parse(DESCRIPTION).render();
+        assert!(rendered.starts_with(&format!(
+            "{ANSI_BOLD}--- Day 1: Trebuchet?! ---{ANSI_RESET}\n\nSomething is wrong with global snow production.\n"
+        )));
+        assert!(rendered.contains("\n    1abc2\n    pqr3stu8vwx\n\n"));
+        assert!(rendered.contains(&format!("\n\n{ANSI_BOLD}--- Part Two ---{ANSI_RESET}\n\n")));
+        assert!(!rendered.contains("```"));
+    }
+}
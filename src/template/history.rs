@@ -0,0 +1,376 @@
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{Error, Write},
+    path::Path,
+    process::Command,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::timings::Timings;
+use crate::template::{Day, PuzzleId, Year};
+
+static HISTORY_FILE_PATH: &str = "./data/benchmark_history.jsonl";
+static GIT_DIR_PATH: &str = "./.git";
+
+/// A benchmark of a single day, recorded together with the environment it was run in.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistoryEntry {
+    pub timestamp: u64,
+    pub commit: Option<String>,
+    pub rustc: Option<String>,
+    pub puzzle: PuzzleId,
+    pub part_1_nanos: Option<f64>,
+    pub part_2_nanos: Option<f64>,
+}
+
+/// Represents all recorded benchmarks.
+/// Stored as an append-only file with one JSON object per line.
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    pub data: Vec<HistoryEntry>,
+}
+
+impl History {
+    /// Appends the median times of `timings` to the history file, tagged with the current time, commit and compiler.
+    pub fn append(timings: &Timings) -> Result<(), Error> {
+        if timings.data.is_empty() {
+            return Ok(());
+        }
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        let commit = read_git_commit(Path::new(GIT_DIR_PATH));
+        let rustc = read_rustc_version();
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(HISTORY_FILE_PATH)?;
+
+        for timing in &timings.data {
+            let entry = HistoryEntry {
+                timestamp,
+                commit: commit.clone(),
+                rustc: rustc.clone(),
+                puzzle: timing.puzzle,
+                part_1_nanos: timing.stats(1).map(|s| s.median),
+                part_2_nanos: timing.stats(2).map(|s| s.median),
+            };
+            writeln!(file, "{}", entry.to_json_line())?;
+        }
+
+        Ok(())
+    }
+
+    /// Rehydrate the history from its file. If not present, returns an empty history.
+    /// Lines that can not be parsed are reported and skipped.
+    pub fn read_from_file() -> Self {
+        let Ok(s) = fs::read_to_string(HISTORY_FILE_PATH) else {
+            return History::default();
+        };
+
+        History::from_lines(&s)
+    }
+
+    fn from_lines(s: &str) -> Self {
+        let data = s
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .filter_map(|(i, line)| match HistoryEntry::from_json_line(line) {
+                Ok(entry) => Some(entry),
+                Err(e) => {
+                    eprintln!("{HISTORY_FILE_PATH}:{}: {e}", i + 1);
+                    None
+                }
+            })
+            .collect();
+
+        History { data }
+    }
+
+    /// All entries of a puzzle, oldest first.
+    pub fn for_puzzle(&self, puzzle: PuzzleId) -> Vec<&HistoryEntry> {
+        let mut entries: Vec<&HistoryEntry> =
+            self.data.iter().filter(|e| e.puzzle == puzzle).collect();
+        entries.sort_by_key(|e| e.timestamp);
+        entries
+    }
+}
+
+impl HistoryEntry {
+    pub fn part_nanos(&self, part: u8) -> Option<f64> {
+        match part {
+            1 => self.part_1_nanos,
+            2 => self.part_2_nanos,
+            _ => None,
+        }
+    }
+
+    fn to_json_line(&self) -> String {
+        JsonValue::from(self).stringify().unwrap_or_default()
+    }
+
+    fn from_json_line(line: &str) -> Result<Self, String> {
+        let json = JsonValue::from_str(line).or(Err("not valid JSON."))?;
+        HistoryEntry::try_from(&json)
+    }
+}
+
+/// Resolves the commit `HEAD` points to by reading the files in the git directory, without calling `git`.
+fn read_git_commit(git_dir: &Path) -> Option<String> {
+    let head = fs::read_to_string(git_dir.join("HEAD")).ok()?;
+    let head = head.trim();
+
+    // a detached `HEAD` contains the commit hash itself.
+    let Some(reference) = head.strip_prefix("ref: ") else {
+        return Some(head.into());
+    };
+
+    if let Ok(hash) = fs::read_to_string(git_dir.join(reference)) {
+        return Some(hash.trim().into());
+    }
+
+    // refs are moved to `packed-refs` by `git gc`.
+    fs::read_to_string(git_dir.join("packed-refs"))
+        .ok()?
+        .lines()
+        .filter_map(|line| line.split_once(' '))
+        .find(|(_, name)| *name == reference)
+        .map(|(hash, _)| hash.into())
+}
+
+fn read_rustc_version() -> Option<String> {
+    let output = Command::new("rustc").arg("--version").output().ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    String::from_utf8(output.stdout)
+        .ok()
+        .map(|s| s.trim().into())
+}
+
+/// Formats a unix timestamp as a UTC date, e.g. `2023-12-01`.
+pub fn format_date(timestamp: u64) -> String {
+    // see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let days = timestamp / 86_400 + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// Renders values as a line of block characters, scaled between their minimum and maximum.
+pub fn sparkline(values: &[f64]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let range = max - min;
+
+    values
+        .iter()
+        .map(|value| {
+            if range <= 0.0 {
+                return BARS[0];
+            }
+
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let index = ((value - min) / range * (BARS.len() - 1) as f64).round() as usize;
+            BARS[index]
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&HistoryEntry> for JsonValue {
+    fn from(value: &HistoryEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        let optional_string = |x: &Option<String>| match x {
+            Some(x) => JsonValue::String(x.clone()),
+            None => JsonValue::Null,
+        };
+        let optional_number = |x: Option<f64>| match x {
+            Some(x) => JsonValue::Number(x),
+            None => JsonValue::Null,
+        };
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert("commit".into(), optional_string(&value.commit));
+        map.insert("rustc".into(), optional_string(&value.rustc));
+        map.insert(
+            "year".into(),
+            JsonValue::String(value.puzzle.year().to_string()),
+        );
+        map.insert(
+            "day".into(),
+            JsonValue::String(value.puzzle.day().to_string()),
+        );
+        map.insert("part_1_nanos".into(), optional_number(value.part_1_nanos));
+        map.insert("part_2_nanos".into(), optional_number(value.part_2_nanos));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HistoryEntry {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history entry to be a JSON object.")?;
+
+        let optional = |key: &str| json.get(key).filter(|v| !v.is_null());
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|timestamp| *timestamp as u64)
+            .ok_or("Expected history.timestamp to be a number.")?;
+
+        let year = json
+            .get("year")
+            .and_then(|v| v.get::<String>())
+            .and_then(|year| Year::from_str(year).ok())
+            .ok_or("Expected history.year to be a Year struct.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected history.day to be a Day struct.")?;
+
+        Ok(HistoryEntry {
+            timestamp,
+            commit: optional("commit").and_then(|v| v.get::<String>()).cloned(),
+            rustc: optional("rustc").and_then(|v| v.get::<String>()).cloned(),
+            puzzle: PuzzleId::new(year, day),
+            part_1_nanos: optional("part_1_nanos")
+                .and_then(|v| v.get::<f64>())
+                .copied(),
+            part_2_nanos: optional("part_2_nanos")
+                .and_then(|v| v.get::<f64>())
+                .copied(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::{env, fs};
+
+    use super::{format_date, read_git_commit, sparkline, History, HistoryEntry};
+    use crate::puzzle;
+
+    fn get_mock_entry() -> HistoryEntry {
+        HistoryEntry {
+            timestamp: 1_701_388_800,
+            commit: Some("4919c6b2d1a8e0f3c5b7a9d2e4f6a8c0b2d4e6f8".into()),
+            rustc: Some("rustc 1.74.0 (79e9716c9 2023-11-13)".into()),
+            puzzle: puzzle!(2023, 1),
+            part_1_nanos: Some(74_130.0),
+            part_2_nanos: None,
+        }
+    }
+
+    #[test]
+    fn round_trips_entries() {
+        let entry = get_mock_entry();
+        let line = entry.to_json_line();
+        assert_eq!(line.contains('\n'), false);
+        assert_eq!(HistoryEntry::from_json_line(&line), Ok(entry));
+    }
+
+    #[test]
+    fn skips_invalid_lines() {
+        let line = get_mock_entry().to_json_line();
+        let history = History::from_lines(&format!("{line}\n\nnot json\n{line}\n"));
+        assert_eq!(history.data.len(), 2);
+    }
+
+    #[test]
+    fn sorts_entries_of_puzzle() {
+        let history = History {
+            data: vec![
+                HistoryEntry {
+                    timestamp: 3,
+                    ..get_mock_entry()
+                },
+                HistoryEntry {
+                    puzzle: puzzle!(2023, 2),
+                    ..get_mock_entry()
+                },
+                HistoryEntry {
+                    timestamp: 2,
+                    ..get_mock_entry()
+                },
+            ],
+        };
+
+        let timestamps: Vec<u64> = history
+            .for_puzzle(puzzle!(2023, 1))
+            .iter()
+            .map(|e| e.timestamp)
+            .collect();
+        assert_eq!(timestamps, vec![2, 3]);
+    }
+
+    #[test]
+    fn formats_dates() {
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(format_date(1_701_388_800), "2023-12-01");
+        assert_eq!(format_date(1_709_164_800), "2024-02-29");
+    }
+
+    #[test]
+    fn renders_sparklines() {
+        assert_eq!(sparkline(&[1.0, 8.0, 4.5]), "▁█▅");
+        assert_eq!(sparkline(&[5.0, 5.0]), "▁▁");
+        assert_eq!(sparkline(&[]), "");
+    }
+
+    #[test]
+    fn reads_git_commits() {
+        let git_dir = env::temp_dir().join(format!("aoc-history-test-{}", std::process::id()));
+        fs::create_dir_all(git_dir.join("refs/heads")).unwrap();
+
+        fs::write(git_dir.join("HEAD"), "ref: refs/heads/main\n").unwrap();
+        fs::write(
+            git_dir.join("packed-refs"),
+            "# pack-refs with: peeled fully-peeled sorted\nabc123 refs/heads/main\n",
+        )
+        .unwrap();
+        assert_eq!(read_git_commit(&git_dir), Some("abc123".into()));
+
+        fs::write(git_dir.join("refs/heads/main"), "def456\n").unwrap();
+        assert_eq!(read_git_commit(&git_dir), Some("def456".into()));
+
+        fs::write(git_dir.join("HEAD"), "789abc\n").unwrap();
+        assert_eq!(read_git_commit(&git_dir), Some("789abc".into()));
+
+        fs::remove_dir_all(&git_dir).unwrap();
+    }
+}
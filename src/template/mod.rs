@@ -1,15 +1,27 @@
 use std::{env, fs};
 
-pub mod aoc_cli;
+pub mod aoc_client;
 pub mod commands;
+pub mod description;
+pub mod registry;
 pub mod runner;
 
+pub use answer::*;
 pub use day::*;
+pub use puzzle_id::*;
+pub use year::*;
 
+mod answer;
+mod answers;
 mod day;
+mod history;
+mod puzzle_id;
 mod readme_benchmarks;
+mod report;
 mod run_multi;
+mod statistics;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
@@ -17,52 +29,115 @@ pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn read_file(folder: &str, puzzle: PuzzleId) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd
+        .join("data")
+        .join(folder)
+        .join(puzzle.year().to_string())
+        .join(format!("{}.txt", puzzle.day()));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, puzzle: PuzzleId, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
         .join("data")
         .join(folder)
-        .join(format!("{day}-{part}.txt"));
+        .join(puzzle.year().to_string())
+        .join(format!("{}-{part}.txt", puzzle.day()));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `YEAR`, `DAY` and `PUZZLE` and sets up the input and runner for each part.
+///
+/// The optional, third parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// If the third parameter is `both`, a single function `solve(input: &str) -> (Option<A>, Option<B>)` solves both parts in one pass.
+/// Its answers are still reported and submitted as part 1 and part 2, sharing the same timing.
 ///
-/// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// If the third parameter is `parse`, the input is first passed to a function `parse(input: &str) -> T`.
+/// Its output is shared by both parts, which then receive a `&T` instead of the raw input. The parse step is timed separately.
+/// E.g. `solution!(2023, 1, parse)`, `solution!(2023, 1, parse, 2)` or `solution!(2023, 1, parse, both)`.
+///
+/// With the `registry` feature, the macro additionally creates a `SOLUTION` constant that allows the main binary to run the solution in-process.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($year:expr, $day:expr) => {
+        $crate::solution!(@impl $year, $day, [] [part_one, 1] [part_two, 2]);
+    };
+    ($year:expr, $day:expr, 1) => {
+        $crate::solution!(@impl $year, $day, [] [part_one, 1]);
+    };
+    ($year:expr, $day:expr, 2) => {
+        $crate::solution!(@impl $year, $day, [] [part_two, 2]);
+    };
+    ($year:expr, $day:expr, both) => {
+        $crate::solution!(@impl $year, $day, [] [both solve]);
+    };
+    ($year:expr, $day:expr, parse) => {
+        $crate::solution!(@impl $year, $day, [parse] [part_one, 1] [part_two, 2]);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    ($year:expr, $day:expr, parse, 1) => {
+        $crate::solution!(@impl $year, $day, [parse] [part_one, 1]);
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    ($year:expr, $day:expr, parse, 2) => {
+        $crate::solution!(@impl $year, $day, [parse] [part_two, 2]);
+    };
+    ($year:expr, $day:expr, parse, both) => {
+        $crate::solution!(@impl $year, $day, [parse] [both solve]);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@impl $year:expr, $day:expr, [$($parse:ident)?] $( [$($part:tt)*] )*) => {
+        /// The current year.
+        const YEAR: $crate::template::Year = $crate::year!($year);
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
+        /// The current puzzle, used to locate its input and example files.
+        const PUZZLE: $crate::template::PuzzleId = $crate::template::PuzzleId::new(YEAR, DAY);
 
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
         fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let input = $crate::template::read_file("inputs", PUZZLE);
+            $( let input = $crate::template::runner::run_parse($parse, &input); )?
+            $( $crate::solution!(@run &input, $($part)*); )*
         }
+
+        /// Entry of this solution in the table of solutions that are run in-process.
+        #[cfg(feature = "registry")]
+        pub const SOLUTION: $crate::template::registry::Solution = $crate::template::registry::Solution {
+            puzzle: PUZZLE,
+            run: |input, is_timed, show_progress, on_report| {
+                $(
+                    let (parsed, report) = $crate::template::runner::report_parse($parse, input, is_timed, show_progress);
+                    on_report(report);
+                    let input = &parsed;
+                )?
+                $( $crate::solution!(@report input, is_timed, show_progress, on_report, $($part)*); )*
+            },
+        };
+    };
+
+    (@run $input:expr, both $func:expr) => {
+        $crate::template::runner::run_both($func, $input, PUZZLE)
+    };
+    (@run $input:expr, $func:expr, $part:expr) => {
+        $crate::template::runner::run_part($func, $input, PUZZLE, $part)
+    };
+
+    (@report $input:ident, $is_timed:ident, $show_progress:ident, $on_report:ident, both $func:expr) => {
+        for report in $crate::template::runner::report_both($func, $input, $is_timed, $show_progress) {
+            $on_report(report);
+        }
+    };
+    (@report $input:ident, $is_timed:ident, $show_progress:ident, $on_report:ident, $func:expr, $part:expr) => {
+        $on_report($crate::template::runner::report_part($func, $input, $part, $is_timed, $show_progress))
     };
 }
@@ -0,0 +1,125 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::template::{Day, Year};
+
+/// Identifies a single puzzle by its year and day of advent.
+///
+/// # Display
+/// This value displays as `{year}-{day}`, which doubles as the name of the solution binary.
+///
+/// ```
+/// # use advent_of_code::template::{Day, PuzzleId, Year};
+/// let puzzle = PuzzleId::new(Year::new(2023).unwrap(), Day::new(8).unwrap());
+/// assert_eq!(puzzle.to_string(), "2023-08")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    year: Year,
+    day: Day,
+}
+
+impl PuzzleId {
+    pub const fn new(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    pub fn year(self) -> Year {
+        self.year
+    }
+
+    pub fn day(self) -> Day {
+        self.day
+    }
+
+    /// Returns the number of parts of this puzzle.
+    /// The puzzle on the last day of a year only has a single part, see [`Year::last_day`].
+    pub fn part_count(self) -> u8 {
+        if self.day == self.year.last_day() {
+            1
+        } else {
+            2
+        }
+    }
+}
+
+#[cfg(feature = "today")]
+impl PuzzleId {
+    /// Returns the puzzle of the current day if it's between the 1st and the 25th of december, `None` otherwise.
+    pub fn today() -> Option<Self> {
+        Some(Self::new(Year::today()?, Day::today()?))
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.year, self.day)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for PuzzleId {
+    type Err = PuzzleIdFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (year, day) = s.split_once('-').ok_or(PuzzleIdFromStrError)?;
+        let year = year.parse().map_err(|_| PuzzleIdFromStrError)?;
+        let day = day.parse().map_err(|_| PuzzleIdFromStrError)?;
+        Ok(Self::new(year, day))
+    }
+}
+
+/// An error which can be returned when parsing a [`PuzzleId`].
+#[derive(Debug)]
+pub struct PuzzleIdFromStrError;
+
+impl Error for PuzzleIdFromStrError {}
+
+impl Display for PuzzleIdFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a puzzle in the format `{year}-{day}`, e.g. `2023-01`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`PuzzleId`] value in a const context.
+#[macro_export]
+macro_rules! puzzle {
+    ($year:expr, $day:expr) => {
+        $crate::template::PuzzleId::new($crate::year!($year), $crate::day!($day))
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::PuzzleId;
+    use crate::puzzle;
+
+    #[test]
+    fn displays_as_bin_name() {
+        assert_eq!(puzzle!(2023, 1).to_string(), "2023-01");
+        assert_eq!(puzzle!(2015, 25).to_string(), "2015-25");
+    }
+
+    #[test]
+    fn parses_bin_name() {
+        assert_eq!("2023-01".parse::<PuzzleId>().unwrap(), puzzle!(2023, 1));
+        assert_eq!("2022-5".parse::<PuzzleId>().unwrap(), puzzle!(2022, 5));
+        assert!("2023".parse::<PuzzleId>().is_err());
+        assert!("2023-26".parse::<PuzzleId>().is_err());
+    }
+
+    #[test]
+    fn counts_parts() {
+        assert_eq!(puzzle!(2023, 1).part_count(), 2);
+        assert_eq!(puzzle!(2023, 12).part_count(), 2);
+        assert_eq!(puzzle!(2023, 25).part_count(), 1);
+        assert_eq!(puzzle!(2025, 11).part_count(), 2);
+        assert_eq!(puzzle!(2025, 12).part_count(), 1);
+    }
+}
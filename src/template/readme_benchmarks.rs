@@ -1,9 +1,10 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{fmt::Display, fs, io};
 
+use crate::template::statistics::{format_nanos, Statistics};
 use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::PuzzleId;
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -19,14 +20,38 @@ impl From<std::io::Error> for Error {
     }
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "{e}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
 pub struct TablePosition {
     pos_start: usize,
     pos_end: usize,
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(puzzle: PuzzleId) -> String {
+    format!("./src/bin/{puzzle}.rs")
+}
+
+/// Formats a table cell as `median ± deviation`, falling back to the plain timing for timings without statistics.
+fn format_cell(timing: Option<String>, stats: Option<Statistics>) -> String {
+    match (timing, stats) {
+        (Some(_), Some(stats)) => {
+            format!(
+                "{} ± {}",
+                format_nanos(stats.median),
+                format_nanos(stats.std_dev)
+            )
+        }
+        (Some(timing), None) => timing,
+        (None, _) => "-".into(),
+    }
 }
 
 fn locate_table(readme: &str) -> Result<TablePosition, Error> {
@@ -54,22 +79,36 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
 fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
     let header = format!("{prefix} Benchmarks");
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    let mut lines: Vec<String> = vec![MARKER.into(), header];
+    let mut current_year = None;
 
+    // NOTE: timings are sorted by puzzle, so every year gets a single, contiguous table.
     for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
+        let year = timing.puzzle.year();
+
+        if current_year != Some(year) {
+            current_year = Some(year);
+            lines.push(String::new());
+            lines.push(format!("{prefix}# {year}"));
+            lines.push(String::new());
+            lines.push("| Day | Parse | Part 1 | Part 2 |".into());
+            lines.push("| :---: | :---: | :---: | :---:  |".into());
+        }
+
+        let path = get_path_for_bin(timing.puzzle);
+        // puzzles without a second part leave its cell empty instead of showing a missing timing.
+        let part_2 = if timing.puzzle.part_count() < 2 {
+            String::new()
+        } else {
+            format!("`{}`", format_cell(timing.part_2, timing.part_2_stats))
+        };
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
+            "| [Day {}]({}) | `{}` | `{}` | {} |",
+            timing.puzzle.day().into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            format_cell(timing.parse, timing.parse_stats),
+            format_cell(timing.part_1, timing.part_1_stats),
+            part_2
         ));
     }
 
@@ -99,27 +138,39 @@ pub fn update(timings: Timings) -> Result<(), Error> {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use crate::{puzzle, template::timings::Timing, template::timings::Timings};
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
+                    puzzle: puzzle!(2023, 1),
+                    parse: None,
+                    parse_stats: None,
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 3e+10,
                 },
                 Timing {
-                    day: day!(2),
+                    puzzle: puzzle!(2023, 2),
+                    parse: None,
+                    parse_stats: None,
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 7e+10,
                 },
                 Timing {
-                    day: day!(4),
+                    puzzle: puzzle!(2023, 4),
+                    parse: None,
+                    parse_stats: None,
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 9e+10,
                 },
             ],
@@ -166,11 +217,13 @@ mod tests {
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "### 2023",
+            "",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/2023-01.rs) | `-` | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/2023-02.rs) | `-` | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/2023-04.rs) | `-` | `40ms` | `50ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -179,4 +232,60 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn groups_benchmarks_by_year() {
+        let mut timings = get_mock_timings();
+        timings.data.insert(
+            0,
+            Timing {
+                puzzle: puzzle!(2022, 25),
+                parse: None,
+                parse_stats: None,
+                part_1: Some("1ms".into()),
+                part_2: None,
+                part_1_stats: None,
+                part_2_stats: None,
+                total_nanos: 1e+6,
+            },
+        );
+
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0).unwrap();
+
+        assert_eq!(s.matches("| Day | Parse | Part 1 | Part 2 |").count(), 2);
+        assert!(s.contains("### 2022\n\n| Day | Parse | Part 1 | Part 2 |\n| :---: | :---: | :---: | :---:  |\n| [Day 25](./src/bin/2022-25.rs) | `-` | `1ms` |  |\n\n### 2023"));
+    }
+
+    #[test]
+    fn formats_statistics() {
+        use crate::template::statistics::Statistics;
+
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_stats = Some(Statistics {
+            samples: 100,
+            outliers: 2,
+            mean: 10_100_000.0,
+            median: 10_000_000.0,
+            std_dev: 500_000.0,
+            min: 9_000_000.0,
+            max: 12_000_000.0,
+            p5: 9_200_000.0,
+            p95: 11_000_000.0,
+        });
+
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0).unwrap();
+        assert!(s.contains("| [Day 1](./src/bin/2023-01.rs) | `-` | `10.0ms ± 500.0µs` | `20ms` |"));
+    }
+
+    #[test]
+    fn formats_parse_times() {
+        let mut timings = get_mock_timings();
+        timings.data[0].parse = Some("5ms".into());
+
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0).unwrap();
+        assert!(s.contains("| [Day 1](./src/bin/2023-01.rs) | `5ms` | `10ms` | `20ms` |"));
+    }
 }
@@ -0,0 +1,88 @@
+use std::{
+    panic::{self, AssertUnwindSafe},
+    sync::OnceLock,
+};
+
+use crate::template::report::PartReport;
+use crate::template::{read_file, PuzzleId};
+
+/// Runs all parts of a solution on an input, passing the report of each part to the callback.
+/// The flags control whether the parts are benched and whether their progress is printed.
+pub type RunFn = fn(&str, bool, bool, &mut dyn FnMut(PartReport));
+
+/// A solution, as registered by the [`solution!`](crate::solution) macro in `registry` mode.
+pub struct Solution {
+    pub puzzle: PuzzleId,
+    pub run: RunFn,
+}
+
+static REGISTRY: OnceLock<&'static [Solution]> = OnceLock::new();
+
+/// Make solutions available to be run in-process. Only the first call has an effect.
+pub fn register(solutions: &'static [Solution]) {
+    let _ = REGISTRY.set(solutions);
+}
+
+/// Find the registered solution of a puzzle.
+pub fn find(puzzle: PuzzleId) -> Option<&'static Solution> {
+    REGISTRY.get()?.iter().find(|s| s.puzzle == puzzle)
+}
+
+impl Solution {
+    /// Run all parts of the solution on the puzzle input, calling `on_report` after each part.
+    /// A panic stops the run instead of taking down the runner, keeping the reports of parts that completed before.
+    pub fn run(
+        &self,
+        is_timed: bool,
+        show_progress: bool,
+        on_report: impl Fn(&PartReport),
+    ) -> Vec<PartReport> {
+        let mut reports = vec![];
+
+        let _ = panic::catch_unwind(AssertUnwindSafe(|| {
+            let input = read_file("inputs", self.puzzle);
+
+            (self.run)(&input, is_timed, show_progress, &mut |report| {
+                on_report(&report);
+                reports.push(report);
+            });
+        }));
+
+        reports
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{find, register, Solution};
+    use crate::puzzle;
+    use crate::template::report::PartReport;
+    use crate::template::runner::report_part;
+
+    #[test]
+    fn finds_registered_solutions() {
+        static SOLUTIONS: &[Solution] = &[Solution {
+            puzzle: puzzle!(2023, 1),
+            run: |input, is_timed, show_progress, on_report| {
+                on_report(report_part(
+                    |x: &str| Some(x.len()),
+                    input,
+                    1,
+                    is_timed,
+                    show_progress,
+                ));
+            },
+        }];
+
+        register(SOLUTIONS);
+
+        let solution = find(puzzle!(2023, 1)).unwrap();
+        let mut reports: Vec<PartReport> = vec![];
+        (solution.run)("abc", false, false, &mut |report| reports.push(report));
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].answer, Some("3".into()));
+        assert_eq!(find(puzzle!(2023, 2)).is_none(), true);
+    }
+}
@@ -0,0 +1,164 @@
+use std::{collections::HashMap, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::statistics::Statistics;
+
+/// Command-line flag that makes solution binaries print a [`PartReport`] per part instead of human-readable text.
+pub const JSON_OUTPUT_FLAG: &str = "--json";
+
+/// Part number used to report the optional parse step of a solution, which does not produce an answer.
+pub const PARSE_PART: u8 = 0;
+
+/// Represents the outcome of running a single part of a solution.
+/// Exchanged between solution binaries and the runner as one JSON object per line.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartReport {
+    pub part: u8,
+    pub answer: Option<String>,
+    pub stats: Statistics,
+    /// Set if the part was solved in the same pass as the previous part and `stats` cover both.
+    pub shares_timing: bool,
+}
+
+impl PartReport {
+    /// Serializes the report to a single line of JSON.
+    pub fn to_json_line(&self) -> String {
+        // NOTE: `stringify` does not emit newlines, multi-line answers are escaped.
+        JsonValue::from(self).stringify().unwrap_or_default()
+    }
+
+    /// Parses a line of output. Returns [`None`] for lines that are not a report, e.g. debug output of a solution.
+    pub fn from_json_line(line: &str) -> Option<Self> {
+        if !line.starts_with('{') {
+            return None;
+        }
+
+        let json = JsonValue::from_str(line).ok()?;
+        PartReport::try_from(&json).ok()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartReport> for JsonValue {
+    fn from(value: &PartReport) -> Self {
+        // statistics are flattened into the report object.
+        let mut map: HashMap<String, JsonValue> = (&value.stats).into();
+
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "shares_timing".into(),
+            JsonValue::Boolean(value.shares_timing),
+        );
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartReport {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected report to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|part| *part as u8)
+            .ok_or("Expected report.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected report.answer to be null or string.")?;
+
+        let shares_timing = json
+            .get("shares_timing")
+            .and_then(|v| v.get::<bool>())
+            .copied()
+            .unwrap_or_default();
+
+        Ok(PartReport {
+            part,
+            answer: answer.cloned(),
+            stats: Statistics::try_from(json)?,
+            shares_timing,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::PartReport;
+    use crate::template::statistics::Statistics;
+
+    fn get_mock_report() -> PartReport {
+        PartReport {
+            part: 1,
+            answer: Some("142".into()),
+            stats: Statistics::from_samples(&[60.0, 74.13, 80.0, 1200.0]).unwrap(),
+            shares_timing: false,
+        }
+    }
+
+    #[test]
+    fn round_trips_reports() {
+        let report = get_mock_report();
+        let line = report.to_json_line();
+        assert_eq!(PartReport::from_json_line(&line), Some(report));
+    }
+
+    #[test]
+    fn round_trips_multi_line_answers() {
+        let report = PartReport {
+            answer: Some("#..#\n(@ @)\n".into()),
+            ..get_mock_report()
+        };
+        let line = report.to_json_line();
+        assert_eq!(line.contains('\n'), false);
+        assert_eq!(PartReport::from_json_line(&line), Some(report));
+    }
+
+    #[test]
+    fn round_trips_missing_answers() {
+        let report = PartReport {
+            answer: None,
+            ..get_mock_report()
+        };
+        let line = report.to_json_line();
+        assert_eq!(PartReport::from_json_line(&line), Some(report));
+    }
+
+    #[test]
+    fn round_trips_shared_timings() {
+        let report = PartReport {
+            part: 2,
+            shares_timing: true,
+            ..get_mock_report()
+        };
+        let line = report.to_json_line();
+        assert_eq!(PartReport::from_json_line(&line), Some(report));
+    }
+
+    #[test]
+    fn ignores_other_output() {
+        assert_eq!(
+            PartReport::from_json_line("Part 1: 0 (74.13ns @ 100000 samples)"),
+            None
+        );
+        assert_eq!(PartReport::from_json_line("{ \"debug\": true }"), None);
+        assert_eq!(PartReport::from_json_line(""), None);
+    }
+}
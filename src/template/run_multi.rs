@@ -1,38 +1,62 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    io,
+    path::Path,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::report::PartReport;
+use crate::template::{registry, Day, PuzzleId, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
-
-    let mut need_space = false;
-
+pub fn run_multi(
+    year: Year,
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    jobs: usize,
+) -> Option<Timings> {
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
-
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
+    let mut timings: Vec<Timing> = Vec::with_capacity(days.len());
+
+    let mut handle_reports = |i: usize, day: Day, reports: Result<Vec<PartReport>, Error>| {
+        match reports {
+            Ok(reports) if reports.is_empty() => println!("Not solved."),
+            Ok(reports) => timings.push(child_commands::parse_exec_time(
+                &reports,
+                PuzzleId::new(year, day),
+            )),
+            Err(e) => eprintln!("failed to run solution: {e}"),
+        }
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        if i + 1 < days.len() {
+            println!();
+        }
+    };
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
-            }
-        });
+    // running days concurrently would skew benchmarks, so timed runs are always sequential.
+    if jobs > 1 && !is_timed {
+        if let Err(e) = run_concurrently(year, &days, is_release, jobs, handle_reports) {
+            eprintln!("failed to run solutions: {e}");
+        }
+    } else {
+        for (i, day) in days.iter().enumerate() {
+            print_header(*day);
+            let reports =
+                child_commands::run_solution(PuzzleId::new(year, *day), is_timed, is_release);
+            handle_reports(i, *day, reports);
+        }
+    }
 
     if is_timed {
         let timings = Timings { data: timings };
@@ -46,9 +70,90 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
     }
 }
 
+fn print_header(day: Day) {
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+}
+
+/// Runs up to `jobs` solutions at the same time. Solutions in the registry are run in-process,
+/// all others are built once and run as executables.
+/// The output of each day is buffered and replayed in order of days once it is complete.
+fn run_concurrently(
+    year: Year,
+    days: &[Day],
+    is_release: bool,
+    jobs: usize,
+    mut handle_reports: impl FnMut(usize, Day, Result<Vec<PartReport>, Error>),
+) -> Result<(), Error> {
+    let needs_build = days.iter().any(|day| {
+        let puzzle = PuzzleId::new(year, *day);
+        registry::find(puzzle).is_none() && Path::new(&get_path_for_bin(puzzle)).exists()
+    });
+
+    let executables = if needs_build {
+        child_commands::build_solutions(is_release)?
+    } else {
+        HashMap::new()
+    };
+
+    let next_index = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            let (sender, next_index, executables) = (sender.clone(), &next_index, &executables);
+
+            scope.spawn(move || loop {
+                let i = next_index.fetch_add(1, Ordering::Relaxed);
+                let Some(day) = days.get(i) else {
+                    break;
+                };
+
+                // days that have not been scaffolded yet do not have an executable.
+                let puzzle = PuzzleId::new(year, *day);
+                let output = match registry::find(puzzle) {
+                    Some(solution) => Some(Ok(child_commands::run_registered(solution))),
+                    None => executables
+                        .get(&puzzle.to_string())
+                        .map(|path| child_commands::run_executable(path)),
+                };
+
+                if sender.send((i, output)).is_err() {
+                    break;
+                }
+            });
+        }
+
+        drop(sender);
+
+        let mut pending = HashMap::new();
+        let mut next_to_print = 0;
+
+        for (i, output) in receiver {
+            pending.insert(i, output);
+
+            while let Some(output) = pending.remove(&next_to_print) {
+                let day = days[next_to_print];
+                print_header(day);
+
+                let reports = match output {
+                    Some(output) => output.map(|output| output.replay()),
+                    None => Ok(vec![]),
+                };
+
+                handle_reports(next_to_print, day, reports);
+                next_to_print += 1;
+            }
+        }
+    });
+
+    Ok(())
+}
+
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
+    BuildFailed,
     IO(io::Error),
 }
 
@@ -58,45 +163,73 @@ impl From<std::io::Error> for Error {
     }
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "could not capture output of child command."),
+            Error::BuildFailed => write!(f, "could not build solutions."),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(puzzle: PuzzleId) -> String {
+    format!("./src/bin/{puzzle}.rs")
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the reports they emit.
 pub mod child_commands {
     use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use crate::template::report::{PartReport, JSON_OUTPUT_FLAG, PARSE_PART};
+    use crate::template::runner::{format_duration, print_parse, print_result};
+    use crate::template::statistics::format_nanos;
+    use crate::template::{registry, PuzzleId};
     use std::{
+        collections::HashMap,
         io::{BufRead, BufReader},
-        path::Path,
+        path::{Path, PathBuf},
         process::{Command, Stdio},
+        str::FromStr,
         thread,
     };
+    use tinyjson::JsonValue;
 
     /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    pub fn run_solution(
+        puzzle: PuzzleId,
+        is_timed: bool,
+        is_release: bool,
+    ) -> Result<Vec<PartReport>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
+        if !Path::new(&get_path_for_bin(puzzle)).exists() {
             return Ok(vec![]);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        // in `registry` mode, solutions compiled into this binary are run in-process.
+        if let Some(solution) = registry::find(puzzle) {
+            return Ok(solution.run(is_timed, true, print_report));
+        }
+
+        let bin_name = puzzle.to_string();
+        let mut args = vec!["run", "--quiet", "--bin", &bin_name];
 
         if is_release {
             args.push("--release");
         }
 
+        // request machine-readable reports from the child.
+        args.push("--");
+        args.push(JSON_OUTPUT_FLAG);
+
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward output to stdout/stderr while collecting reports from stdout lines.
 
         let mut cmd = Command::new("cargo")
             .args(&args)
@@ -107,7 +240,7 @@ pub mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        let mut reports = vec![];
 
         let thread = thread::spawn(move || {
             stderr.lines().for_each(|line| {
@@ -116,78 +249,178 @@ pub mod child_commands {
         });
 
         for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+            handle_line(&line.unwrap(), &mut reports);
         }
 
         thread.join().unwrap();
         cmd.wait()?;
 
-        Ok(output)
+        Ok(reports)
+    }
+
+    /// Print a line of child output, collecting it if it is a report.
+    fn handle_line(line: &str, reports: &mut Vec<PartReport>) {
+        match PartReport::from_json_line(line) {
+            Some(report) => {
+                print_report(&report);
+                reports.push(report);
+            }
+            None => println!("{line}"),
+        }
+    }
+
+    /// Build all solution bins, returning the paths of their executables by bin name.
+    pub fn build_solutions(is_release: bool) -> Result<HashMap<String, PathBuf>, Error> {
+        let mut args = vec![
+            "build",
+            "--quiet",
+            "--bins",
+            "--message-format=json-render-diagnostics",
+        ];
+
+        if is_release {
+            args.push("--release");
+        }
+
+        // compiler diagnostics are rendered to stderr, build messages are emitted to stdout.
+        let output = Command::new("cargo")
+            .args(&args)
+            .stderr(Stdio::inherit())
+            .output()?;
+
+        if !output.status.success() {
+            return Err(Error::BuildFailed);
+        }
+
+        Ok(parse_executables(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    /// Extract the executables of bins from the JSON messages emitted by `cargo build`.
+    fn parse_executables(messages: &str) -> HashMap<String, PathBuf> {
+        messages
+            .lines()
+            .filter_map(|line| JsonValue::from_str(line).ok())
+            .filter_map(|json| {
+                let message = json.get::<HashMap<String, JsonValue>>()?;
+
+                if message.get("reason")?.get::<String>()? != "compiler-artifact" {
+                    return None;
+                }
+
+                let executable = message.get("executable")?.get::<String>()?;
+                let name = message
+                    .get("target")?
+                    .get::<HashMap<String, JsonValue>>()?
+                    .get("name")?
+                    .get::<String>()?;
+
+                Some((name.clone(), PathBuf::from(executable)))
+            })
+            .collect()
+    }
+
+    /// Output of a solution that ran in the background.
+    pub struct BufferedOutput {
+        stdout: String,
+        stderr: String,
+    }
+
+    impl BufferedOutput {
+        /// Print the output as if the solution had run in the foreground, returning its reports.
+        pub fn replay(&self) -> Vec<PartReport> {
+            let mut reports = vec![];
+
+            for line in self.stdout.lines() {
+                handle_line(line, &mut reports);
+            }
+
+            for line in self.stderr.lines() {
+                eprintln!("{line}");
+            }
+
+            reports
+        }
+    }
+
+    /// Run a built solution executable, buffering its output.
+    pub fn run_executable(executable: &Path) -> Result<BufferedOutput, Error> {
+        let output = Command::new(executable).arg(JSON_OUTPUT_FLAG).output()?;
+
+        Ok(BufferedOutput {
+            stdout: String::from_utf8_lossy(&output.stdout).into(),
+            stderr: String::from_utf8_lossy(&output.stderr).into(),
+        })
+    }
+
+    /// Run a solution from the registry without printing progress, buffering its reports as if it had run as an executable.
+    pub fn run_registered(solution: &registry::Solution) -> BufferedOutput {
+        let reports = solution.run(false, false, |_| {});
+
+        BufferedOutput {
+            stdout: reports.iter().map(|r| r.to_json_line() + "\n").collect(),
+            stderr: String::new(),
+        }
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
+    /// Print a report the same way the solution binary would have printed it.
+    fn print_report(report: &PartReport) {
+        let duration_str = format_duration(&report.stats);
+
+        if report.part == PARSE_PART {
+            print_parse(&duration_str);
+        } else {
+            print_result(
+                &report.answer,
+                &format!("Part {}", report.part),
+                &duration_str,
+            );
+        }
+    }
+
+    /// Return the answer reported for a part, if any.
+    pub fn answer_for(reports: &[PartReport], part: u8) -> Option<&str> {
+        reports
+            .iter()
+            .find(|r| r.part == part)
+            .and_then(|r| r.answer.as_deref())
+    }
+
+    pub fn parse_exec_time(reports: &[PartReport], puzzle: PuzzleId) -> super::Timing {
         let mut timings = super::Timing {
-            day,
+            puzzle,
+            parse: None,
             part_1: None,
             part_2: None,
+            parse_stats: None,
+            part_1_stats: None,
+            part_2_stats: None,
             total_nanos: 0_f64,
         };
 
-        output
+        reports
             .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
+            .filter(|report| report.part == PARSE_PART || report.answer.is_some())
+            .for_each(|report| {
+                let timing_str = format_nanos(report.stats.median);
+                let stats = Some(report.stats.clone());
+
+                match report.part {
+                    PARSE_PART => (timings.parse, timings.parse_stats) = (Some(timing_str), stats),
+                    1 => (timings.part_1, timings.part_1_stats) = (Some(timing_str), stats),
+                    2 => (timings.part_2, timings.part_2_stats) = (Some(timing_str), stats),
+                    _ => {}
                 }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+                // parts solved in one pass are timed together, count their time once.
+                // the total uses the median, like the times shown for each part.
+                if !report.shares_timing {
+                    timings.total_nanos += report.stats.median;
                 }
-
-                timings.total_nanos += nanos;
             });
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
-
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
     #[cfg(feature = "test_lib")]
     macro_rules! assert_approx_eq {
@@ -204,53 +437,142 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{answer_for, parse_exec_time, parse_executables, BufferedOutput};
 
-        use crate::day;
+        use crate::{
+            puzzle,
+            template::{report::PartReport, statistics::Statistics},
+        };
+
+        #[cfg(test)]
+        fn report(part: u8, answer: Option<&str>, nanos: f64) -> PartReport {
+            PartReport {
+                part,
+                answer: answer.map(Into::into),
+                stats: Statistics::from_samples(&[nanos]).unwrap(),
+                shares_timing: false,
+            }
+        }
 
         #[test]
         fn parses_execution_times() {
             let res = parse_exec_time(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    report(1, Some("0"), 74.0),
+                    report(2, Some("10"), 74_130_000.0),
                 ],
-                day!(1),
+                puzzle!(2023, 1),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_approx_eq!(res.total_nanos, 74130074_f64);
+            assert_eq!(res.part_1.unwrap(), "74.0ns");
+            assert_eq!(res.part_2.unwrap(), "74.1ms");
+            assert_eq!(res.part_2_stats.unwrap().median, 74_130_000.0);
         }
 
         #[test]
         fn parses_with_patterns_in_input() {
             let res = parse_exec_time(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    report(1, Some("@ @ @ ( ) ms"), 2_000_000_000.0),
+                    report(2, Some("10s (1 samples)"), 100_000_000.0),
                 ],
-                day!(1),
+                puzzle!(2023, 1),
             );
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.part_1.unwrap(), "2.0s");
+            assert_eq!(res.part_2.unwrap(), "100.0ms");
         }
 
         #[test]
         fn parses_missing_parts() {
             let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
+                &[report(1, None, 10.0), report(2, None, 10.0)],
+                puzzle!(2023, 1),
             );
             assert_approx_eq!(res.total_nanos, 0_f64);
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn parses_parse_times() {
+            let res = parse_exec_time(
+                &[
+                    report(0, None, 1_000.0),
+                    report(1, Some("0"), 74.0),
+                    report(2, None, 10.0),
+                ],
+                puzzle!(2023, 1),
+            );
+            assert_approx_eq!(res.total_nanos, 1074_f64);
+            assert_eq!(res.parse.unwrap(), "1.0µs");
+            assert_eq!(res.parse_stats.unwrap().median, 1_000.0);
+            assert_eq!(res.part_1.unwrap(), "74.0ns");
+            assert_eq!(res.part_2.is_none(), true);
+        }
+
+        #[test]
+        fn parses_shared_times() {
+            let mut reports = [report(1, Some("1"), 500.0), report(2, Some("2"), 500.0)];
+            reports[1].shares_timing = true;
+
+            let res = parse_exec_time(&reports, puzzle!(2023, 1));
+            assert_approx_eq!(res.total_nanos, 500_f64);
+            assert_eq!(res.part_1.unwrap(), "500.0ns");
+            assert_eq!(res.part_2.unwrap(), "500.0ns");
+        }
+
+        #[test]
+        fn sums_median_times() {
+            let mut reports = [report(1, Some("1"), 0.0), report(2, Some("2"), 0.0)];
+            reports[0].stats = Statistics::from_samples(&[100.0, 200.0, 900.0]).unwrap();
+            reports[1].stats = Statistics::from_samples(&[10.0, 20.0, 90.0]).unwrap();
+
+            let res = parse_exec_time(&reports, puzzle!(2023, 1));
+            assert_approx_eq!(res.total_nanos, 220_f64);
+            assert_eq!(res.part_1.unwrap(), "200.0ns");
+            assert_eq!(res.part_2.unwrap(), "20.0ns");
+        }
+
+        #[test]
+        fn finds_answers() {
+            let reports = [report(1, Some("(a @ b)"), 1.0), report(2, None, 1.0)];
+            assert_eq!(answer_for(&reports, 1), Some("(a @ b)"));
+            assert_eq!(answer_for(&reports, 2), None);
+        }
+
+        #[test]
+        fn parses_executables() {
+            use std::path::PathBuf;
+
+            let messages = [
+                r#"{"reason":"compiler-artifact","target":{"kind":["lib"],"name":"advent_of_code"},"executable":null}"#,
+                r#"{"reason":"compiler-artifact","target":{"kind":["bin"],"name":"2023-01"},"executable":"/target/debug/2023-01"}"#,
+                r#"{"reason":"build-finished","success":true}"#,
+            ]
+            .join("\n");
+
+            let executables = parse_executables(&messages);
+            assert_eq!(executables.len(), 1);
+            assert_eq!(
+                executables.get("2023-01"),
+                Some(&PathBuf::from("/target/debug/2023-01"))
+            );
+        }
+
+        #[test]
+        fn replays_buffered_output() {
+            let output = BufferedOutput {
+                stdout: format!(
+                    "debug output\n{}\n",
+                    report(1, Some("42"), 10.0).to_json_line()
+                ),
+                stderr: String::new(),
+            };
+
+            let reports = output.replay();
+            assert_eq!(reports.len(), 1);
+            assert_eq!(answer_for(&reports, 1), Some("42"));
+        }
     }
 }
@@ -2,34 +2,255 @@
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
+use crate::template::answers::{Answers, Verdict};
+use crate::template::report::{PartReport, JSON_OUTPUT_FLAG, PARSE_PART};
+use crate::template::statistics::{format_nanos, Statistics};
+use crate::template::Answer;
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_client, PuzzleId, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+pub fn run_part<I: Clone, T: Into<Answer>>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    puzzle: PuzzleId,
+    part: u8,
+) {
+    let is_json_output = is_json_output();
+    let is_timed = env::args().any(|x| x == "--time");
+
+    let (answer, report) = answer_part(func, input, part, is_timed, !is_json_output);
+    output_part(answer, &report, puzzle, is_json_output);
+}
+
+/// Run a part of a solution and collect its answer and timing.
+/// If `show_progress` is set, the answer is printed as soon as it is known, before benching starts.
+pub fn report_part<I: Clone, T: Into<Answer>>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    part: u8,
+    is_timed: bool,
+    show_progress: bool,
+) -> PartReport {
+    answer_part(func, input, part, is_timed, show_progress).1
+}
+
+fn answer_part<I: Clone, T: Into<Answer>>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    part: u8,
+    is_timed: bool,
+    show_progress: bool,
+) -> (Option<Answer>, PartReport) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (answer, stats) = run_timed(
+        |input| func(input).map(Into::into),
+        input,
+        is_timed,
+        show_progress,
+        |answer| {
+            if show_progress {
+                print_result(answer, &part_str, "");
+            }
+        },
+    );
+
+    let report = to_report(part, answer.as_ref(), stats, false);
+    (answer, report)
+}
+
+/// Run a solution that solves both parts in one pass, then report and submit each part individually.
+pub fn run_both<I: Clone, A: Into<Answer>, B: Into<Answer>>(
+    func: impl Fn(I) -> (Option<A>, Option<B>),
+    input: I,
+    puzzle: PuzzleId,
+) {
+    let is_json_output = is_json_output();
+    let is_timed = env::args().any(|x| x == "--time");
+
+    let (answers, reports) = answer_both(func, input, is_timed, !is_json_output);
+
+    for (answer, report) in answers.into_iter().zip(&reports) {
+        output_part(answer, report, puzzle, is_json_output);
+    }
+}
+
+/// Run a solution that solves both parts in one pass and collect a report per part. Both share the same timing.
+pub fn report_both<I: Clone, A: Into<Answer>, B: Into<Answer>>(
+    func: impl Fn(I) -> (Option<A>, Option<B>),
+    input: I,
+    is_timed: bool,
+    show_progress: bool,
+) -> [PartReport; 2] {
+    answer_both(func, input, is_timed, show_progress).1
+}
+
+fn answer_both<I: Clone, A: Into<Answer>, B: Into<Answer>>(
+    func: impl Fn(I) -> (Option<A>, Option<B>),
+    input: I,
+    is_timed: bool,
+    show_progress: bool,
+) -> ([Option<Answer>; 2], [PartReport; 2]) {
+    let ((part_1, part_2), stats) = run_timed(
+        |input| {
+            let (part_1, part_2) = func(input);
+            (part_1.map(Into::into), part_2.map(Into::into))
+        },
+        input,
+        is_timed,
+        show_progress,
+        |answers| {
+            if show_progress {
+                print_result(&answers.0, "Part 1", "");
+            }
+        },
+    );
+
+    let reports = [
+        to_report(1, part_1.as_ref(), stats.clone(), false),
+        to_report(2, part_2.as_ref(), stats, true),
+    ];
+
+    ([part_1, part_2], reports)
+}
+
+fn to_report(
+    part: u8,
+    answer: Option<&Answer>,
+    stats: Statistics,
+    shares_timing: bool,
+) -> PartReport {
+    // art that can not be decoded is reported as drawn.
+    let answer = answer.map(|answer| answer.submission().unwrap_or_else(|_| answer.to_string()));
+
+    PartReport {
+        part,
+        answer,
+        stats,
+        shares_timing,
+    }
+}
+
+/// Print the outcome of a part and submit it if requested.
+fn output_part(
+    answer: Option<Answer>,
+    report: &PartReport,
+    puzzle: PuzzleId,
+    is_json_output: bool,
+) {
+    // when invoked by `run_multi`, report results in a machine-readable format and skip submission.
+    if is_json_output {
+        println!("{}", report.to_json_line());
+        return;
+    }
+
+    print_result(
+        &answer,
+        &format!("Part {}", report.part),
+        &format_duration(&report.stats),
+    );
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let Some(answer) = answer else {
+        return;
+    };
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    if let Answer::Art(_) = answer {
+        match answer.submission() {
+            Ok(text) => println!("Decoded: {ANSI_BOLD}{text}{ANSI_RESET}"),
+            Err(e) => eprintln!("Could not decode answer: {e}"),
+        }
     }
+
+    submit_result(&answer, puzzle, report.part);
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+/// Run the parse step of a solution and report its timing. Returns the parsed input, which is shared by all parts.
+pub fn run_parse<'a, T>(func: impl Fn(&'a str) -> T, input: &'a str) -> T {
+    let is_json_output = is_json_output();
+    let is_timed = env::args().any(|x| x == "--time");
+
+    let (parsed, report) = report_parse(func, input, is_timed, !is_json_output);
+
+    if is_json_output {
+        println!("{}", report.to_json_line());
+    } else {
+        print_parse(&format_duration(&report.stats));
+    }
+
+    parsed
+}
+
+/// Run the parse step of a solution and collect its timing. See [`report_part`].
+pub fn report_parse<'a, T>(
+    func: impl Fn(&'a str) -> T,
+    input: &'a str,
+    is_timed: bool,
+    show_progress: bool,
+) -> (T, PartReport) {
+    let (parsed, stats) = run_timed(func, input, is_timed, show_progress, |_| {
+        if show_progress {
+            print!("Parse");
+        }
+    });
+
+    let report = PartReport {
+        part: PARSE_PART,
+        answer: None,
+        stats,
+        shares_timing: false,
+    };
+
+    (parsed, report)
+}
+
+fn is_json_output() -> bool {
+    env::args().any(|x| x == JSON_OUTPUT_FLAG)
+}
+
+/// Benchmark settings. Can be configured with the following environment variables:
+///  - `AOC_BENCH_BUDGET_MS`: approx. time spent collecting samples (default: 1000).
+///  - `AOC_BENCH_WARMUP_MS`: approx. time spent on warmup runs before sampling (default: 100).
+///  - `AOC_BENCH_MIN_SAMPLES` / `AOC_BENCH_MAX_SAMPLES`: bounds for the number of samples (default: 10 / 10000).
+struct BenchConfig {
+    budget: Duration,
+    warmup: Duration,
+    min_samples: u128,
+    max_samples: u128,
+}
+
+impl BenchConfig {
+    fn from_env() -> Self {
+        let var = |key: &str, default: u64| {
+            env::var(key)
+                .ok()
+                .and_then(|x| x.parse().ok())
+                .unwrap_or(default)
+        };
+
+        let min_samples = u128::from(var("AOC_BENCH_MIN_SAMPLES", 10).max(1));
+
+        Self {
+            budget: Duration::from_millis(var("AOC_BENCH_BUDGET_MS", 1000)),
+            warmup: Duration::from_millis(var("AOC_BENCH_WARMUP_MS", 100)),
+            min_samples,
+            max_samples: u128::from(var("AOC_BENCH_MAX_SAMPLES", 10000)).max(min_samples),
+        }
+    }
+}
+
+/// Run a solution part. The behavior differs depending on whether the run is timed:
+///  1. if not, the function is executed once.
+///  2. if it is, the function is benched (see [`BenchConfig`] for the default time budget and sample bounds.)
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    is_timed: bool,
+    show_progress: bool,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Statistics) {
     let timer = Instant::now();
     let result = {
         let input = input.clone();
@@ -43,63 +264,89 @@ fn run_timed<I: Clone, T>(
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let stats = if is_timed {
+        bench(
+            func,
+            input,
+            &base_time,
+            &BenchConfig::from_env(),
+            show_progress,
+        )
     } else {
-        (base_time, 1)
+        Statistics::single(base_time)
     };
 
-    (result, run.0, run.1)
+    (result, stats)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
-
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
-
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
-
-    let mut timers: Vec<Duration> = vec![];
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    config: &BenchConfig,
+    show_progress: bool,
+) -> Statistics {
+    if show_progress {
+        let mut stdout = stdout();
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout.flush();
+    }
 
-    for _ in 0..bench_iterations {
+    let run = || {
         // need a clone here to make the borrow checker happy.
         let cloned = input.clone();
         let timer = Instant::now();
         black_box(func(black_box(cloned)));
-        timers.push(timer.elapsed());
+        timer.elapsed()
+    };
+
+    // warm up caches and branch predictors, and get a better estimate of the time per iteration than the first run.
+    let mut per_iteration = *base_time;
+    let warmup_iterations = estimate_iterations(&config.warmup, base_time, 1, config.max_samples);
+
+    if !config.warmup.is_zero() {
+        let warmup_time: Duration = (0..warmup_iterations).map(|_| run()).sum();
+        // the count is at least 1, and saturating keeps it from truncating to 0.
+        let warmup_time = warmup_time / u32::try_from(warmup_iterations).unwrap_or(u32::MAX);
+        per_iteration = warmup_time;
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    let bench_iterations = estimate_iterations(
+        &config.budget,
+        &per_iteration,
+        config.min_samples,
+        config.max_samples,
+    );
+
+    #[allow(clippy::cast_precision_loss)]
+    let timers: Vec<f64> = (0..bench_iterations)
+        .map(|_| run().as_nanos() as f64)
+        .collect();
+
+    Statistics::from_samples(&timers).unwrap()
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+fn estimate_iterations(budget: &Duration, per_iteration: &Duration, min: u128, max: u128) -> u128 {
+    (budget.as_nanos() / cmp::max(per_iteration.as_nanos(), 10)).clamp(min, max)
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+pub(crate) fn format_duration(stats: &Statistics) -> String {
+    let median = format_nanos(stats.median);
+
+    if stats.samples == 1 {
+        format!(" ({median})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        let std_dev = format_nanos(stats.std_dev);
+        format!(" ({median} ± {std_dev} @ {} samples)", stats.samples)
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+pub(crate) fn print_parse(duration_str: &str) {
+    print!("\r");
+    println!("Parse{duration_str}");
+}
+
+pub(crate) fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
@@ -136,16 +383,13 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+///  2. a session cookie is configured.
+///  3. the answer is not ruled out by previous submissions stored in `data/answers.json`.
+fn submit_result(result: &Answer, puzzle: PuzzleId, part: u8) {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
-        return None;
+        return;
     }
 
     if args.len() < 3 {
@@ -161,14 +405,45 @@ fn submit_result<T: Display>(
     };
 
     if part_submit != part {
-        return None;
+        return;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    let answer = match result.submission() {
+        Ok(answer) => answer,
+        Err(e) => {
+            eprintln!("Refusing to submit result: could not decode answer: {e}");
+            process::exit(1);
+        }
+    };
+
+    let mut answers = match Answers::read_from_file() {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("Refusing to submit result: {e}");
+            process::exit(1);
+        }
+    };
+
+    if let Err(refusal) = answers.check(puzzle, part, &answer) {
+        eprintln!("Refusing to submit result: {refusal}");
         process::exit(1);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    println!("Submitting result...");
+    let message = match aoc_client::submit(puzzle, part, &answer) {
+        Ok(message) => message,
+        Err(e) => {
+            eprintln!("failed to submit result: {e}");
+            process::exit(1);
+        }
+    };
+
+    println!("{message}");
+
+    if let Some(verdict) = Verdict::from_message(&message) {
+        answers.record(puzzle, part, &answer, verdict);
+        if let Err(e) = answers.store_file() {
+            eprintln!("failed to store answers: {e}");
+        }
+    }
 }
@@ -0,0 +1,209 @@
+use std::{collections::HashMap, time::Duration};
+use tinyjson::JsonValue;
+
+/// Summary statistics over the samples of a benchmark, in nanoseconds.
+/// Outliers are rejected before any of the statistics are computed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Statistics {
+    pub samples: u64,
+    pub outliers: u64,
+    pub mean: f64,
+    pub median: f64,
+    pub std_dev: f64,
+    pub min: f64,
+    pub max: f64,
+    pub p5: f64,
+    pub p95: f64,
+}
+
+impl Statistics {
+    /// Computes statistics for a list of samples. Returns [`None`] if there are no samples.
+    ///
+    /// Samples outside of [Tukey's fences](https://en.wikipedia.org/wiki/Outlier#Tukey's_fences)
+    /// (1.5 times the interquartile range below the first or above the third quartile) are rejected as outliers.
+    pub fn from_samples(samples: &[f64]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable_by(f64::total_cmp);
+
+        let q1 = percentile(&sorted, 25.0);
+        let q3 = percentile(&sorted, 75.0);
+        let iqr = q3 - q1;
+        let (low, high) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+
+        let retained: Vec<f64> = sorted
+            .iter()
+            .copied()
+            .filter(|x| (low..=high).contains(x))
+            .collect();
+
+        #[allow(clippy::cast_precision_loss)]
+        let len = retained.len() as f64;
+        let mean = retained.iter().sum::<f64>() / len;
+        let variance = retained.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / len;
+
+        Some(Self {
+            samples: retained.len() as u64,
+            outliers: (sorted.len() - retained.len()) as u64,
+            mean,
+            median: percentile(&retained, 50.0),
+            std_dev: variance.sqrt(),
+            min: retained[0],
+            max: retained[retained.len() - 1],
+            p5: percentile(&retained, 5.0),
+            p95: percentile(&retained, 95.0),
+        })
+    }
+
+    /// Statistics of a single, un-benched run.
+    pub fn single(duration: Duration) -> Self {
+        #[allow(clippy::cast_precision_loss)]
+        let nanos = duration.as_nanos() as f64;
+        Self::from_samples(&[nanos]).unwrap()
+    }
+}
+
+/// Returns the `p`-th percentile (0 to 100) of sorted samples, interpolating linearly between the closest ranks.
+pub fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.len() == 1 {
+        return sorted[0];
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - rank.floor())
+}
+
+/// Formats nanoseconds the same way durations are printed by the runner, e.g. `74.1µs`.
+pub fn format_nanos(nanos: f64) -> String {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let duration = Duration::from_nanos(nanos as u64);
+    format!("{duration:.1?}")
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Statistics> for HashMap<String, JsonValue> {
+    fn from(value: &Statistics) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        {
+            map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+            map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+        }
+        map.insert("mean_nanos".into(), JsonValue::Number(value.mean));
+        map.insert("median_nanos".into(), JsonValue::Number(value.median));
+        map.insert("std_dev_nanos".into(), JsonValue::Number(value.std_dev));
+        map.insert("min_nanos".into(), JsonValue::Number(value.min));
+        map.insert("max_nanos".into(), JsonValue::Number(value.max));
+        map.insert("p5_nanos".into(), JsonValue::Number(value.p5));
+        map.insert("p95_nanos".into(), JsonValue::Number(value.p95));
+
+        map
+    }
+}
+
+impl From<&Statistics> for JsonValue {
+    fn from(value: &Statistics) -> Self {
+        JsonValue::Object(value.into())
+    }
+}
+
+impl TryFrom<&HashMap<String, JsonValue>> for Statistics {
+    type Error = String;
+
+    fn try_from(json: &HashMap<String, JsonValue>) -> Result<Self, Self::Error> {
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected statistics.{key} to be a number."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(Statistics {
+            samples: number("samples")? as u64,
+            outliers: number("outliers")? as u64,
+            mean: number("mean_nanos")?,
+            median: number("median_nanos")?,
+            std_dev: number("std_dev_nanos")?,
+            min: number("min_nanos")?,
+            max: number("max_nanos")?,
+            p5: number("p5_nanos")?,
+            p95: number("p95_nanos")?,
+        })
+    }
+}
+
+impl TryFrom<&JsonValue> for Statistics {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected statistics to be a JSON object.")?
+            .try_into()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{format_nanos, percentile, Statistics};
+
+    #[test]
+    fn interpolates_percentiles() {
+        let sorted = [1.0, 2.0, 3.0, 4.0];
+        assert_eq!(percentile(&sorted, 0.0), 1.0);
+        assert_eq!(percentile(&sorted, 50.0), 2.5);
+        assert_eq!(percentile(&sorted, 100.0), 4.0);
+        assert_eq!(percentile(&[7.0], 95.0), 7.0);
+    }
+
+    #[test]
+    fn computes_statistics() {
+        let stats = Statistics::from_samples(&[4.0, 2.0, 6.0, 8.0]).unwrap();
+        assert_eq!(stats.samples, 4);
+        assert_eq!(stats.outliers, 0);
+        assert_eq!(stats.mean, 5.0);
+        assert_eq!(stats.median, 5.0);
+        assert_eq!(stats.std_dev, 5.0_f64.sqrt());
+        assert_eq!(stats.min, 2.0);
+        assert_eq!(stats.max, 8.0);
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let stats = Statistics::from_samples(&[10.0, 11.0, 10.0, 12.0, 11.0, 500.0]).unwrap();
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.max, 12.0);
+        assert_eq!(stats.median, 11.0);
+    }
+
+    #[test]
+    fn handles_empty_samples() {
+        assert_eq!(Statistics::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn round_trips_json() {
+        let stats = Statistics::from_samples(&[1.0, 2.0, 3.0]).unwrap();
+        let json = tinyjson::JsonValue::from(&stats);
+        assert_eq!(Statistics::try_from(&json).unwrap(), stats);
+    }
+
+    #[test]
+    fn formats_nanos() {
+        assert_eq!(format_nanos(74.0), "74.0ns");
+        assert_eq!(format_nanos(74_130_000.0), "74.1ms");
+    }
+}
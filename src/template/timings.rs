@@ -1,16 +1,22 @@
 use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::report::PARSE_PART;
+use crate::template::statistics::Statistics;
+use crate::template::{Day, PuzzleId, Year};
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
-    pub day: Day,
+    pub puzzle: PuzzleId,
+    pub parse: Option<String>,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    pub parse_stats: Option<Statistics>,
+    pub part_1_stats: Option<Statistics>,
+    pub part_2_stats: Option<Statistics>,
     pub total_nanos: f64,
 }
 
@@ -53,12 +59,12 @@ impl Timings {
         }
 
         for timing in &self.data {
-            if !data.iter().any(|t| t.day == timing.day) {
+            if !data.iter().any(|t| t.puzzle == timing.puzzle) {
                 data.push(timing.clone());
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|t| t.puzzle);
         Timings { data }
     }
 
@@ -67,10 +73,74 @@ impl Timings {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
-    pub fn is_day_complete(&self, day: Day) -> bool {
-        self.data
-            .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+    /// Whether every part of a puzzle has a stored timing.
+    pub fn is_day_complete(&self, puzzle: PuzzleId) -> bool {
+        self.data.iter().any(|t| {
+            t.puzzle == puzzle
+                && t.part_1.is_some()
+                && (puzzle.part_count() < 2 || t.part_2.is_some())
+        })
+    }
+
+    /// Compare the median times of parts that have statistics in both `self` (the baseline) and `current`.
+    pub fn compare(&self, current: &Self) -> Vec<PartComparison> {
+        let mut comparisons: Vec<PartComparison> = vec![];
+
+        for timing in &current.data {
+            let Some(baseline) = self.data.iter().find(|t| t.puzzle == timing.puzzle) else {
+                continue;
+            };
+
+            for part in [PARSE_PART, 1, 2] {
+                if let (Some(before), Some(after)) = (baseline.stats(part), timing.stats(part)) {
+                    // a zero median can not be compared relatively.
+                    if before.median > 0.0 {
+                        comparisons.push(PartComparison {
+                            puzzle: timing.puzzle,
+                            part,
+                            baseline_nanos: before.median,
+                            current_nanos: after.median,
+                        });
+                    }
+                }
+            }
+        }
+
+        comparisons.sort_unstable_by_key(|c| (c.puzzle, c.part));
+        comparisons
+    }
+}
+
+impl Timing {
+    /// Statistics of a part, or of the parse step for [`PARSE_PART`].
+    pub fn stats(&self, part: u8) -> Option<&Statistics> {
+        match part {
+            PARSE_PART => self.parse_stats.as_ref(),
+            1 => self.part_1_stats.as_ref(),
+            2 => self.part_2_stats.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+/// Change of the median time of a part between a stored and a new benchmark.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartComparison {
+    pub puzzle: PuzzleId,
+    pub part: u8,
+    pub baseline_nanos: f64,
+    pub current_nanos: f64,
+}
+
+impl PartComparison {
+    /// Relative change in percent. Negative values are speedups, positive values slowdowns.
+    pub fn change_percent(&self) -> f64 {
+        (self.current_nanos - self.baseline_nanos) / self.baseline_nanos * 100.0
+    }
+
+    /// Whether the part got slower by more than `threshold` percent.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change_percent() > threshold
     }
 }
 
@@ -118,12 +188,28 @@ impl From<&Timing> for JsonValue {
     fn from(value: &Timing) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
-        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert(
+            "year".into(),
+            JsonValue::String(value.puzzle.year().to_string()),
+        );
+        map.insert(
+            "day".into(),
+            JsonValue::String(value.puzzle.day().to_string()),
+        );
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
+        let parse = value.parse.clone().map(JsonValue::String);
         let part_1 = value.part_1.clone().map(JsonValue::String);
         let part_2 = value.part_2.clone().map(JsonValue::String);
 
+        map.insert(
+            "parse".into(),
+            match parse {
+                Some(x) => x,
+                None => JsonValue::Null,
+            },
+        );
+
         map.insert(
             "part_1".into(),
             match part_1 {
@@ -140,6 +226,20 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        for (key, stats) in [
+            ("parse_stats", &value.parse_stats),
+            ("part_1_stats", &value.part_1_stats),
+            ("part_2_stats", &value.part_2_stats),
+        ] {
+            map.insert(
+                key.into(),
+                match stats {
+                    Some(x) => JsonValue::from(x),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -158,6 +258,18 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
+        // timings stored before multi-year support do not carry a year, assume the configured one.
+        let year = match json.get("year") {
+            Some(v) => v.get::<String>().and_then(|year| Year::from_str(year).ok()),
+            None => Year::from_env(),
+        }
+        .ok_or("Expected timing.year to be a Year struct.")?;
+
+        // the parse step is optional and missing in timings stored before it was introduced.
+        let parse = json
+            .get("parse")
+            .and_then(|v| if v.is_null() { None } else { v.get::<String>() });
+
         let part_1 = json
             .get("part_1")
             .map(|v| if v.is_null() { None } else { v.get::<String>() })
@@ -168,15 +280,35 @@ impl TryFrom<&JsonValue> for Timing {
             .map(|v| if v.is_null() { None } else { v.get::<String>() })
             .ok_or("Expected timing.part_2 to be null or string.")?;
 
+        let parse_stats = match json.get("parse_stats") {
+            Some(v) if !v.is_null() => Some(Statistics::try_from(v)?),
+            _ => None,
+        };
+
+        // statistics are not present in timings stored before they were introduced.
+        let part_1_stats = match json.get("part_1_stats") {
+            Some(v) if !v.is_null() => Some(Statistics::try_from(v)?),
+            _ => None,
+        };
+
+        let part_2_stats = match json.get("part_2_stats") {
+            Some(v) if !v.is_null() => Some(Statistics::try_from(v)?),
+            _ => None,
+        };
+
         let total_nanos = json
             .get("total_nanos")
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
         Ok(Timing {
-            day,
+            puzzle: PuzzleId::new(year, day),
+            parse: parse.cloned(),
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            parse_stats,
+            part_1_stats,
+            part_2_stats,
             total_nanos,
         })
     }
@@ -186,7 +318,7 @@ impl TryFrom<&JsonValue> for Timing {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use crate::day;
+    use crate::puzzle;
 
     use super::{Timing, Timings};
 
@@ -194,21 +326,33 @@ mod tests {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
+                    puzzle: puzzle!(2023, 1),
+                    parse: None,
+                    parse_stats: None,
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 3e+10,
                 },
                 Timing {
-                    day: day!(2),
+                    puzzle: puzzle!(2023, 2),
+                    parse: None,
+                    parse_stats: None,
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 7e+10,
                 },
                 Timing {
-                    day: day!(4),
+                    puzzle: puzzle!(2023, 4),
+                    parse: None,
+                    parse_stats: None,
                     part_1: Some("40ms".into()),
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 4e+10,
                 },
             ],
@@ -216,15 +360,15 @@ mod tests {
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{puzzle, template::timings::Timings};
 
         #[test]
         fn handles_json_timings() {
-            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let json = r#"{ "data": [{ "year": "2023", "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
-            assert_eq!(timing.day, day!(1));
+            assert_eq!(timing.puzzle, puzzle!(2023, 1));
             assert_eq!(timing.part_1, Some("1ms".to_string()));
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
@@ -277,7 +421,7 @@ mod tests {
 
     mod is_day_complete {
         use crate::{
-            day,
+            puzzle,
             template::timings::{Timing, Timings},
         };
 
@@ -285,48 +429,136 @@ mod tests {
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
+                    puzzle: puzzle!(2023, 1),
+                    parse: None,
+                    parse_stats: None,
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 3_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(puzzle!(2023, 1)), true);
         }
 
         #[test]
         fn handles_partial_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
+                    puzzle: puzzle!(2023, 1),
+                    parse: None,
+                    parse_stats: None,
+                    part_1: Some("1ms".into()),
+                    part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    total_nanos: 1_000_000_000_f64,
+                }],
+            };
+
+            assert_eq!(timings.is_day_complete(puzzle!(2023, 1)), false);
+        }
+
+        #[test]
+        fn handles_single_part_days() {
+            let timings = Timings {
+                data: vec![Timing {
+                    puzzle: puzzle!(2023, 25),
+                    parse: None,
+                    parse_stats: None,
                     part_1: Some("1ms".into()),
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 1_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(puzzle!(2023, 25)), true);
         }
 
         #[test]
         fn handles_uncompleted_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
+                    puzzle: puzzle!(2023, 1),
+                    parse: None,
+                    parse_stats: None,
                     part_1: None,
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 0.0,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(puzzle!(2023, 1)), false);
+        }
+    }
+
+    #[cfg(test)]
+    mod compare {
+        use crate::{
+            puzzle,
+            template::{
+                statistics::Statistics,
+                timings::{Timing, Timings},
+            },
+        };
+
+        fn get_timings(part_1: Option<f64>, part_2: Option<f64>) -> Timings {
+            Timings {
+                data: vec![Timing {
+                    puzzle: puzzle!(2023, 1),
+                    parse: None,
+                    parse_stats: None,
+                    part_1: Some("1ms".into()),
+                    part_2: Some("2ms".into()),
+                    part_1_stats: part_1.and_then(|x| Statistics::from_samples(&[x])),
+                    part_2_stats: part_2.and_then(|x| Statistics::from_samples(&[x])),
+                    total_nanos: 0.0,
+                }],
+            }
+        }
+
+        #[test]
+        fn computes_relative_changes() {
+            let baseline = get_timings(Some(1000.0), Some(2000.0));
+            let current = get_timings(Some(500.0), Some(2500.0));
+
+            let comparisons = baseline.compare(&current);
+            assert_eq!(comparisons.len(), 2);
+
+            assert_eq!(comparisons[0].part, 1);
+            assert_eq!(comparisons[0].change_percent(), -50.0);
+            assert_eq!(comparisons[0].is_regression(10.0), false);
+
+            assert_eq!(comparisons[1].part, 2);
+            assert_eq!(comparisons[1].change_percent(), 25.0);
+            assert_eq!(comparisons[1].is_regression(10.0), true);
+            assert_eq!(comparisons[1].is_regression(30.0), false);
+        }
+
+        #[test]
+        fn skips_parts_without_statistics() {
+            let baseline = get_timings(None, Some(2000.0));
+            let current = get_timings(Some(500.0), None);
+            assert_eq!(baseline.compare(&current), vec![]);
+        }
+
+        #[test]
+        fn skips_days_without_baseline() {
+            let baseline = Timings::default();
+            let current = get_timings(Some(500.0), Some(2500.0));
+            assert_eq!(baseline.compare(&current), vec![]);
         }
     }
 
     mod merge {
         use crate::{
-            day,
+            puzzle,
             template::timings::{Timing, Timings},
         };
 
@@ -337,18 +569,22 @@ mod tests {
             let timings = get_mock_timings();
             let other = Timings {
                 data: vec![Timing {
-                    day: day!(3),
+                    puzzle: puzzle!(2023, 3),
+                    parse: None,
+                    parse_stats: None,
                     part_1: None,
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 0_f64,
                 }],
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
-            assert_eq!(merged.data[0].day, day!(1));
-            assert_eq!(merged.data[1].day, day!(2));
-            assert_eq!(merged.data[2].day, day!(3));
-            assert_eq!(merged.data[3].day, day!(4));
+            assert_eq!(merged.data[0].puzzle, puzzle!(2023, 1));
+            assert_eq!(merged.data[1].puzzle, puzzle!(2023, 2));
+            assert_eq!(merged.data[2].puzzle, puzzle!(2023, 3));
+            assert_eq!(merged.data[3].puzzle, puzzle!(2023, 4));
         }
 
         #[test]
@@ -357,19 +593,23 @@ mod tests {
 
             let other = Timings {
                 data: vec![Timing {
-                    day: day!(2),
+                    puzzle: puzzle!(2023, 2),
+                    parse: None,
+                    parse_stats: None,
                     part_1: None,
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
                     total_nanos: 0_f64,
                 }],
             };
             let merged = timings.merge(&other);
 
             assert_eq!(merged.data.len(), 3);
-            assert_eq!(merged.data[0].day, day!(1));
-            assert_eq!(merged.data[1].day, day!(2));
+            assert_eq!(merged.data[0].puzzle, puzzle!(2023, 1));
+            assert_eq!(merged.data[1].puzzle, puzzle!(2023, 2));
             assert_eq!(merged.data[1].total_nanos, 0_f64);
-            assert_eq!(merged.data[2].day, day!(4));
+            assert_eq!(merged.data[2].puzzle, puzzle!(2023, 4));
         }
 
         #[test]
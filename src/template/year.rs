@@ -0,0 +1,165 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use super::Day;
+
+#[cfg(feature = "today")]
+use chrono::{Datelike, FixedOffset, Utc};
+
+#[cfg(feature = "today")]
+use super::day::SERVER_UTC_OFFSET;
+
+/// The first year advent of code took place.
+const FIRST_YEAR: u16 = 2015;
+
+/// The first year advent of code ended on the 12th instead of the 25th.
+const FIRST_TWELVE_DAY_YEAR: u16 = 2025;
+
+/// A valid year of advent (i.e. an integer starting from 2015).
+///
+/// # Display
+/// This value displays as a four digit number.
+///
+/// ```
+/// # use advent_of_code::template::Year;
+/// let year = Year::new(2023).unwrap();
+/// assert_eq!(year.to_string(), "2023")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's in the valid range,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if !(FIRST_YEAR..=9999).contains(&year) {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// Returns the final day of advent in this year, whose puzzle only has a single part.
+    /// Since 2025, advent of code ends on the 12th.
+    pub fn last_day(self) -> Day {
+        if self.0 >= FIRST_TWELVE_DAY_YEAR {
+            Day::__new_unchecked(12)
+        } else {
+            Day::__new_unchecked(25)
+        }
+    }
+
+    /// Reads the default year from the `AOC_YEAR` environment variable.
+    /// Returns [`None`] if the variable is not set or does not contain a valid year.
+    pub fn from_env() -> Option<Self> {
+        std::env::var("AOC_YEAR").ok()?.parse().ok()
+    }
+}
+
+#[cfg(feature = "today")]
+impl Year {
+    /// Returns the current year in the timezone of the advent of code server.
+    pub fn today() -> Option<Self> {
+        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
+        let today = Utc::now().with_timezone(&offset);
+        Self::new(u16::try_from(today.year()).ok()?)
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}", self.0)
+    }
+}
+
+impl PartialEq<u16> for Year {
+    fn eq(&self, other: &u16) -> bool {
+        self.0.eq(other)
+    }
+}
+
+impl PartialOrd<u16> for Year {
+    fn partial_cmp(&self, other: &u16) -> Option<std::cmp::Ordering> {
+        self.0.partial_cmp(other)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year number starting from 2015")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {{
+        const _ASSERT: () = assert!(
+            $year >= 2015 && $year <= 9999,
+            concat!(
+                "invalid year number `",
+                $year,
+                "`, expecting a value starting from 2015"
+            ),
+        );
+        $crate::template::Year::__new_unchecked($year)
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::Year;
+    use crate::day;
+
+    #[test]
+    fn parses_valid_years() {
+        assert_eq!("2015".parse::<Year>().unwrap(), 2015);
+        assert_eq!("2023".parse::<Year>().unwrap(), 2023);
+    }
+
+    #[test]
+    fn rejects_invalid_years() {
+        assert!("2014".parse::<Year>().is_err());
+        assert!("23".parse::<Year>().is_err());
+        assert!("year".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn knows_last_day() {
+        assert_eq!(Year::new(2015).unwrap().last_day(), day!(25));
+        assert_eq!(Year::new(2024).unwrap().last_day(), day!(25));
+        assert_eq!(Year::new(2025).unwrap().last_day(), day!(12));
+    }
+}